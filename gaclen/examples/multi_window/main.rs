@@ -0,0 +1,146 @@
+//! Multiple windows drawn using a single device.
+//! 
+//! Each window has its own swapchain, while the device, passes and resources are shared.
+//! Reuses the shaders of the quad example.
+
+// Allow `shader!` macro to use this project's gaclen dependency.
+extern crate gaclen;
+
+mod shaders;
+
+use gaclen::graphics;
+use gaclen::winit;
+
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::event::{Event, WindowEvent};
+use winit::window::{Window, WindowBuilder};
+
+use std::sync::Arc;
+
+#[derive(Default, Debug, Clone)]
+struct Vertex {
+	position: [f32; 3],
+	color: [f32; 4],
+}
+gaclen::graphics::impl_vertex!(Vertex, position, color);
+
+// A window along with everything required to draw to it.
+struct Target {
+	window: Arc<Window>,
+	swapchain: graphics::swapchain::Swapchain,
+	recreate_swapchain: bool,
+	clear_color: [f32; 4],
+}
+
+fn main() {
+	let clock = graphics::timing::FrameClock::new();
+
+	let event_loop = EventLoop::new();
+	let (window, context, device, swapchain) = graphics::bootstrap(
+		WindowBuilder::new()
+			.with_title("Multi-window example (first)")
+			.with_inner_size(winit::dpi::PhysicalSize::new(640, 480)),
+		&event_loop,
+		graphics::PresentMode::Immediate,
+		graphics::image::Format::D16Unorm,
+	).expect("Failed to initialize graphics!");
+	println!("Initialized device: {:?}", device);
+
+	// The second window reuses the context and device of the first one.
+	let second_window = Arc::new(
+		WindowBuilder::new()
+			.with_title("Multi-window example (second)")
+			.with_inner_size(winit::dpi::PhysicalSize::new(640, 480))
+			.build(&event_loop).unwrap()
+	);
+	let second_swapchain = graphics::swapchain::Swapchain::new(&context, &device, second_window.clone(), graphics::PresentMode::Immediate, graphics::image::Format::D16Unorm, false).expect("Failed to create swapchain!");
+
+	let mut targets = vec![
+		Target { window, swapchain, recreate_swapchain: false, clear_color: [0.1, 0.0, 0.0, 1.0] },
+		Target { window: second_window, swapchain: second_swapchain, recreate_swapchain: false, clear_color: [0.0, 0.0, 0.1, 1.0] },
+	];
+
+	// Both swapchains are created by the same device for similar windows, so their images are expected to share the format.
+	let pass = {
+		let vs = shaders::vertex::Shader::load(&device).unwrap();
+		let fs = shaders::fragment::Shader::load(&device).unwrap();
+
+		graphics::pass::GraphicalPass::start()
+			.single_buffer_input::<Vertex>()
+			.vertex_shader(vs.main_entry_point(), ())
+			.fragment_shader(fs.main_entry_point(), ())
+			.add_image_attachment_swapchain_cleared(&targets[0].swapchain)
+			.add_depth_attachment_swapchain_discard(&targets[0].swapchain, graphics::pass::LoadOp::Clear).unwrap()
+			.build(&device).unwrap()
+	};
+
+	let triangle_buffer = device.create_vertex_buffer(
+		[
+			Vertex { position: [-0.5, 0.5, 0.0 ], color: [ 0.25, 0.75, 0.25, 1.0 ] },
+			Vertex { position: [ 0.5,-0.5, 0.0 ], color: [ 0.75, 0.25, 0.25, 1.0 ] },
+			Vertex { position: [ 0.5, 0.5, 0.0 ], color: [ 0.75, 0.75, 0.25, 0.0 ] },
+		].iter().cloned()
+	).unwrap();
+
+	// Wrap the device in a stack-allocated container to allow for temporary ownership.
+	let mut device = Some(device);
+
+	event_loop.run(move |event, _, control_flow| {
+		*control_flow = ControlFlow::Poll;
+		match event {
+			Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => *control_flow = ControlFlow::Exit,
+			Event::WindowEvent { event: WindowEvent::Resized(_), window_id } => {
+				for target in targets.iter_mut().filter(|target| target.window.id() == window_id) {
+					target.recreate_swapchain = true;
+				};
+			},
+			Event::RedrawEventsCleared => {
+				let push_constants = push_constants_from_time(clock.elapsed().as_secs_f32());
+
+				// Every window is drawn in a separate frame, the device is passed from one frame to the next.
+				for target in targets.iter_mut() {
+					if target.recreate_swapchain {
+						match target.swapchain.resize(target.window.inner_size().into()) {
+							Ok(()) => target.recreate_swapchain = false,
+							Err(_) => continue,
+						};
+					}
+
+					let frame = match graphics::frame::Frame::begin(device.take().unwrap(), &target.swapchain) {
+						Ok(frame) => frame,
+						Err((returned, _)) => {
+							device = Some(returned);
+							target.recreate_swapchain = true;
+							continue;
+						},
+					};
+
+					let framebuffer = Arc::new(pass.start_framebuffer()
+						.add(target.swapchain.get_color_image_for(&frame)).unwrap()
+						.add(target.swapchain.get_depth_image_for(&frame).expect("The swapchain was created without depth!")).unwrap()
+						.build().unwrap()
+					);
+
+					let after_frame = frame.begin_pass(&pass, framebuffer, target.swapchain.default_viewport(), vec![target.clear_color.into(), 1.0f32.into()])
+						.draw(vec![triangle_buffer.clone()], (), push_constants)
+						.finish_pass()
+					.finish();
+
+					device = match after_frame {
+						Ok(device) => Some(device),
+						Err((device, err)) => {
+							if err == graphics::frame::FrameFinishError::Flush(gaclen::graphics::vulkano::sync::FlushError::OutOfDate) { target.recreate_swapchain = true; };
+							Some(device)
+						},
+					};
+				};
+			},
+			_ => ()
+		}
+	});
+}
+
+fn push_constants_from_time(time: f32) -> shaders::vertex::ty::PushConstantData {
+	let rotation = cgmath::Matrix4::from_angle_z(cgmath::Rad(time));
+	shaders::vertex::ty::PushConstantData { MVP: rotation.into() }
+}
@@ -1,6 +1,6 @@
 use gaclen::graphics::device::Device as GaclenDevice;
 use gaclen::graphics::vulkano::memory::DeviceMemoryAllocError;
-use gaclen::graphics::buffer::{BufferUsage, CpuAccessibleBuffer};
+use gaclen::graphics::buffer::{ImmutableBuffer, create_immutable_vertex_buffer};
 
 use std::sync::Arc;
 
@@ -13,8 +13,9 @@ pub struct Vertex {
 
 gaclen::graphics::impl_vertex!(Vertex, position, normal, uv);
 
-pub fn generate_cube(device: &GaclenDevice) -> Result<Arc<CpuAccessibleBuffer<[Vertex]>>, DeviceMemoryAllocError> {
-	CpuAccessibleBuffer::from_iter(device.logical_device(), BufferUsage::all(), false, [
+// Static geometry is best kept in device-local memory.
+pub fn generate_cube(device: &GaclenDevice) -> Result<Arc<ImmutableBuffer<[Vertex]>>, DeviceMemoryAllocError> {
+	create_immutable_vertex_buffer(device, [
 		// X-
 		Vertex { position: [ -0.5,  0.5, -0.5 ], normal: [ -1.0, 0.0, 0.0 ], uv: [ 1.0, 1.0 ] },
 		Vertex { position: [ -0.5, -0.5, -0.5 ], normal: [ -1.0, 0.0, 0.0 ], uv: [ 0.0, 1.0 ] },
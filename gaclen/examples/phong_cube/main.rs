@@ -3,7 +3,7 @@
 //! This example showcases all functionality required to render a believable 3D world.
 //! It is limited to a single cube, a real scene would be a lot more complex, likely with additional helper code and resources.
 //! 
-//! Please note, that because of screen-space coordinate mismatch between OpenGL and Vulkan the `up` coordinate and triangle-faces are reversed.
+//! The projection matrix is produced by `gaclen::graphics::camera::vulkan_projection()`, so neither the `up` coordinate nor the triangle-faces need to be reversed.
 
 // Allow `shader!` macro to use this project's gaclen dependency.
 extern crate gaclen;
@@ -23,22 +23,19 @@ use winit::window::WindowBuilder;
 use std::sync::Arc;
 
 fn main() {
-	let mut frame_count: u64 = 0;
-	let start_time = std::time::Instant::now();
+	let mut clock = graphics::timing::FrameClock::new();
 
 	let event_loop = EventLoop::new();
-	let window = std::sync::Arc::new(
+	let (window, _context, device, mut swapchain) = graphics::bootstrap(
 		WindowBuilder::new()
 			.with_title("Cube example")
 			.with_inner_size(winit::dpi::PhysicalSize::new(1280, 720))
-			.with_min_inner_size(winit::dpi::PhysicalSize::new(1280, 720))
-			.build(&event_loop).unwrap()
-	);
-	
-	let context = graphics::context::Context::new().unwrap();
-	let device = graphics::device::Device::new(&context).unwrap();
+			.with_min_inner_size(winit::dpi::PhysicalSize::new(1280, 720)),
+		&event_loop,
+		graphics::PresentMode::Immediate,
+		graphics::image::Format::D16Unorm,
+	).expect("Failed to initialize graphics!");
 	println!("Initialized device: {:?}", device);
-	let mut swapchain = graphics::swapchain::Swapchain::new(&context, &device, window.clone(), graphics::swapchain::PresentMode::Immediate, graphics::image::Format::D16Unorm).expect("Failed to create swapchain!");
 
 	let albedo_pass = {
 		let vs = shaders::vertex::Shader::load(&device).unwrap();
@@ -49,7 +46,6 @@ fn main() {
 			.vertex_shader(vs.main_entry_point(), ())
 			.fragment_shader(fs.main_entry_point(), ())
 			.basic_depth_test()
-			.front_face_clockwise()
 			.cull_back()
 			.add_image_attachment_swapchain_cleared(&swapchain)
 			.add_depth_attachment_swapchain_discard(&swapchain, graphics::pass::LoadOp::Clear).unwrap()
@@ -59,15 +55,16 @@ fn main() {
 	let geometry = geometry::generate_cube(&device).unwrap();
 
 	let transform_buffer_pool = graphics::buffer::CpuBufferPool::<shaders::vertex::ty::TransformData>::new(device.logical_device(), graphics::buffer::BufferUsage::all());
+	let mut transform_descriptor_pool = albedo_pass.fixed_descriptor_pool(0);
 	let light_buffer_pool = graphics::buffer::CpuBufferPool::<shaders::fragment::ty::LightData>::new(device.logical_device(), graphics::buffer::BufferUsage::all());
 
 	let texture = {
 		let image = image::open("gaclen/examples/phong_cube/texture.png").unwrap().to_rgba();
 		let (width, height) = image.dimensions();
-        let dimensions = graphics::image::Dimensions::Dim2d { width, height };
 		let image_data = image.into_raw(); // to_rgba() returns Vec<u8> backed container
-		
-		graphics::image::create_immutable_image_from_iter(&device, image_data.iter().cloned(), dimensions, graphics::image::Format::R8G8B8A8Srgb).unwrap()
+
+		// The texture stores albedo colors, so it is sRGB-encoded.
+		graphics::image::create_texture(&device, image_data.into_iter(), [width, height], graphics::color::ColorSpace::Srgb).unwrap()
 	};
 
 	let sampler = graphics::image::Sampler::simple_repeat_linear(device.logical_device());
@@ -90,9 +87,7 @@ fn main() {
 
 	let mut recreate_swapchain = false;
 
-	let mut rotation_enabled = false;
-	let mut last_x = 0;
-	let mut last_y = 0;
+	let mut input = gaclen::window::input::InputState::new();
 	let mut object_rotation = cgmath::Quaternion::one();
 
 	// Wrap the device in a stack-allocated container to allow for temporary ownership.
@@ -100,36 +95,30 @@ fn main() {
 
 	event_loop.run(move |event, _, control_flow| {
 		*control_flow = ControlFlow::Poll;
+		input.process_event(&event);
 		match event {
 			Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
 				*control_flow = ControlFlow::Exit;
-				let run_duration = start_time.elapsed().as_secs_f64();
-				let fps: f64 = frame_count as f64 / run_duration;
-				println!("Produced {} frames over {:.2} seconds ({:.2} avg fps)", frame_count, run_duration, fps);
+				println!("Produced {} frames over {:.2} seconds ({:.2} avg fps)", clock.total_frames(), clock.elapsed().as_secs_f64(), clock.average_fps());
 			},
 			Event::WindowEvent { event: WindowEvent::Resized(_), .. } => recreate_swapchain = true,
-			Event::WindowEvent { event: WindowEvent::MouseInput{state, button, .. }, .. } => {
-				rotation_enabled = (button == winit::event::MouseButton::Right) && state == winit::event::ElementState::Pressed;
-			}
-			Event::WindowEvent { event: WindowEvent::CursorMoved{ position, .. }, .. } => {
-				let (x, y) = position.into();
-				
-				if rotation_enabled {
+			Event::RedrawEventsCleared => {
+				if input.is_mouse_button_down(gaclen::window::input::MouseButton::Right) {
 					let (width, height) : (f64, f64) = window.inner_size().into();
-					let delta_x = (x as f32 - last_x as f32) / width as f32;
-					let delta_y = (y as f32 - last_y as f32) / height as f32;
-					let delta : cgmath::Quaternion<_> = cgmath::Euler::new(cgmath::Rad(0.0), cgmath::Rad(delta_y), -cgmath::Rad(delta_x)).into();
+					let (cursor_x, cursor_y) = input.cursor_delta();
+					let delta_x = (cursor_x / width) as f32;
+					let delta_y = (cursor_y / height) as f32;
+					let delta : cgmath::Quaternion<_> = cgmath::Euler::new(cgmath::Rad(0.0), cgmath::Rad(delta_y), cgmath::Rad(delta_x)).into();
 					object_rotation = delta * object_rotation;
 				}
+				input.end_frame();
 
-				last_x = x;
-				last_y = y;
-			},
-			Event::RedrawEventsCleared => {
 				if recreate_swapchain {
 					let dimensions = window.inner_size();
 					match swapchain.resize(dimensions.into()) {
 						Ok(()) => (),
+						// There is nothing to draw to until the window is restored.
+						Err(graphics::ResizeError::Minimized) => return,
 						Err(graphics::ResizeError::Swapchain(_)) => {
 							println!("Failed to resize window, skipping frame!");
 							return;
@@ -146,17 +135,17 @@ fn main() {
 					transform_buffer_pool.next(data).unwrap()
 				};
 		
-				let transform_descriptor_set = Arc::new(albedo_pass.start_persistent_descriptor_set(0)
+				// Reuse descriptor sets from the pool instead of allocating new ones every frame.
+				let transform_descriptor_set = transform_descriptor_pool.next()
 					.add_buffer(transform).unwrap()
-					.build().unwrap()
-				);
+					.build().unwrap();
 		
 				// Device ownership is taken here.
 				let frame = graphics::frame::Frame::begin(device.take().unwrap(), &swapchain).unwrap();
 		
 				let framebuffer = std::sync::Arc::new(albedo_pass.start_framebuffer()
 					.add(swapchain.get_color_image_for(&frame)).unwrap()
-					.add(swapchain.get_depth_image_for(&frame)).unwrap()
+					.add(swapchain.get_depth_image_for(&frame).expect("The swapchain was created without depth!")).unwrap()
 					.build().unwrap()
 				);
 		
@@ -174,7 +163,7 @@ fn main() {
 					},
 				};
 		
-				frame_count += 1;
+				clock.tick();
 			},
 			_ => ()
 		}
@@ -186,14 +175,14 @@ fn transform(rotation: cgmath::Quaternion<f32>, viewport_dimensions: (u32, u32))
 	let aspect = viewport_dimensions.0 as f32 / viewport_dimensions.1 as f32;
 
 	let model: cgmath::Matrix4<f32> = rotation.into();
-	let proj: cgmath::Matrix4<f32> = cgmath::PerspectiveFov { fovy: cgmath::Deg(40.0).into(), aspect, near: 0.1, far: 4.0 }.into();
+	let proj = graphics::camera::vulkan_projection(cgmath::Deg(40.0), aspect, 0.1, 4.0);
 
 	shaders::vertex::ty::TransformData {
 		model: model.into(),
 		view: cgmath::Matrix4::look_at(
 			cgmath::Point3 { x: 3.0, y: 0.0, z: 0.0 },
 			cgmath::Point3 { x: 0.0, y: 0.0, z: 0.0 },
-			cgmath::Vector3 { x: 0.0, y: 0.0, z: -1.0 }).into(),
+			cgmath::Vector3 { x: 0.0, y: 0.0, z: 1.0 }).into(),
 		proj: proj.into(),
 	}
 }
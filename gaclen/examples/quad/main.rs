@@ -9,7 +9,7 @@ extern crate gaclen;
 
 mod shaders;
 
-use gaclen::graphics;
+use gaclen::graphics::prelude::*;
 use gaclen::winit;
 
 use winit::event_loop::{ControlFlow, EventLoop};
@@ -24,40 +24,34 @@ struct Vertex {
 gaclen::graphics::impl_vertex!(Vertex, position, color);
 
 fn main() {
-	let mut frame_count: u64 = 0;
-	let start_time = std::time::Instant::now();
+	let mut clock = FrameClock::new();
 
 	let event_loop = EventLoop::new();
-	let window = std::sync::Arc::new(
+	let (window, _context, device, mut swapchain) = bootstrap(
 		WindowBuilder::new()
 			.with_title("Quad example")
 			.with_inner_size(winit::dpi::PhysicalSize::new(1280, 720))
-			.with_min_inner_size(winit::dpi::PhysicalSize::new(1280, 720))
-			.build(&event_loop).unwrap()
-	);
-	
-	let context = graphics::context::Context::new().unwrap();
-	let device = graphics::device::Device::new(&context).unwrap();
+			.with_min_inner_size(winit::dpi::PhysicalSize::new(1280, 720)),
+		&event_loop,
+		PresentMode::Immediate,
+		Format::D16Unorm,
+	).expect("Failed to initialize graphics!");
 	println!("Initialized device: {:?}", device);
-	let mut swapchain = graphics::swapchain::Swapchain::new(&context, &device, window.clone(), graphics::swapchain::PresentMode::Immediate, graphics::image::Format::D16Unorm).expect("Failed to create swapchain!");
 
 	let pass = {
 		let vs = shaders::vertex::Shader::load(&device).unwrap();
 		let fs = shaders::fragment::Shader::load(&device).unwrap();
 
-		graphics::pass::GraphicalPass::start()
+		GraphicalPass::start()
 			.single_buffer_input::<Vertex>()
 			.vertex_shader(vs.main_entry_point(), ())
 			.fragment_shader(fs.main_entry_point(), ())
 			.add_image_attachment_swapchain_cleared(&swapchain)
-			.add_depth_attachment_swapchain_discard(&swapchain, graphics::pass::LoadOp::Clear).unwrap()
+			.add_depth_attachment_swapchain_discard(&swapchain, LoadOp::Clear).unwrap()
 			.build(&device).unwrap()
 	};
 
-	let triangle_buffer = graphics::buffer::CpuAccessibleBuffer::from_data(
-		device.logical_device(),
-		graphics::buffer::BufferUsage::all(),
-		false,
+	let triangle_buffer = device.create_vertex_buffer(
 		[
 			Vertex { position: [-0.5, 0.5, 0.0 ], color: [ 0.25, 0.75, 0.25, 1.0 ] },
 			Vertex { position: [ 0.5,-0.5, 0.0 ], color: [ 0.75, 0.25, 0.25, 1.0 ] },
@@ -66,9 +60,11 @@ fn main() {
 			Vertex { position: [-0.5,-0.5, 0.0 ], color: [ 0.0, 0.0, 0.0, 1.0 ] },
 			Vertex { position: [ 0.5,-0.5, 0.0 ], color: [ 1.0, 0.0, 0.0, 1.0 ] },
 			Vertex { position: [-0.5, 0.5, 0.0 ], color: [ 0.0, 1.0, 0.0, 1.0 ] },
-		]
+		].iter().cloned()
 	).unwrap();
 
+	// The pass draws to every swapchain image the same way, so the framebuffers are built once per swapchain image.
+	let mut framebuffers = swapchain.build_framebuffers(&pass).unwrap();
 	let mut recreate_swapchain = false;
 
 	// Wrap the device in a stack-allocated container to allow for temporary ownership.
@@ -79,9 +75,7 @@ fn main() {
 		match event {
 			Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
 				*control_flow = ControlFlow::Exit;
-				let run_duration = start_time.elapsed().as_secs_f64();
-				let fps: f64 = frame_count as f64 / run_duration;
-				println!("Produced {} frames over {:.2} seconds ({:.2} avg fps)", frame_count, run_duration, fps);
+				println!("Produced {} frames over {:.2} seconds ({:.2} avg fps)", clock.total_frames(), clock.elapsed().as_secs_f64(), clock.average_fps());
 			},
 			Event::WindowEvent { event: WindowEvent::Resized(_), .. } => recreate_swapchain = true,
 			Event::RedrawEventsCleared => {
@@ -90,25 +84,31 @@ fn main() {
 		
 					match swapchain.resize(dimensions.into()) {
 						Ok(()) => (),
-						Err(graphics::ResizeError::Swapchain(_)) => {
+						// There is nothing to draw to until the window is restored.
+						Err(ResizeError::Minimized) => return,
+						Err(ResizeError::Swapchain(_)) => {
 							println!("Failed to resize window, skipping frame!");
 							return;
 						},
 						Err(err) => panic!(err),
 					};
+					framebuffers = swapchain.build_framebuffers(&pass).unwrap();
 					recreate_swapchain = false;
 				}
 		
 				let clear_color = [0.0, 0.0, 0.0, 1.0];
-				let push_constants = push_constants_from_time(start_time.elapsed().as_secs_f32(), window.inner_size().into());
-		
-				let frame = graphics::frame::Frame::begin(device.take().unwrap(), &swapchain).unwrap();
+				let push_constants = push_constants_from_time(clock.elapsed().as_secs_f32(), window.inner_size().into());
 		
-				let framebuffer = std::sync::Arc::new(pass.start_framebuffer()
-					.add(swapchain.get_color_image_for(&frame)).unwrap()
-					.add(swapchain.get_depth_image_for(&frame)).unwrap()
-					.build().unwrap()
-				);
+				let frame = match Frame::try_begin(device.take().unwrap(), &swapchain) {
+					BeginResult::Ready(frame) => frame,
+					BeginResult::RecreateSwapchain(returned) => {
+						device = Some(returned);
+						recreate_swapchain = true;
+						return;
+					},
+					BeginResult::Failed(_, err) => panic!("Failed to begin a frame: {}", err),
+				};
+				let framebuffer = framebuffers[frame.swapchain_index()].clone();
 		
 				let after_frame = frame.begin_pass(&pass, framebuffer, swapchain.default_viewport(), vec![clear_color.into(), 1.0f32.into()])
 					.draw(vec![triangle_buffer.clone()], (), push_constants)
@@ -118,12 +118,12 @@ fn main() {
 				device = match after_frame {
 					Ok(device) => Some(device),
 					Err((device, err)) => {
-						if err == graphics::frame::FrameFinishError::Flush(gaclen::graphics::vulkano::sync::FlushError::OutOfDate) { recreate_swapchain = true; };
+						if err == FrameFinishError::Flush(FlushError::OutOfDate) { recreate_swapchain = true; };
 						Some(device)
 					},
 				};
 		
-				frame_count += 1;
+				clock.tick();
 			}
 			_ => ()
 		}
@@ -1,6 +1,6 @@
 use gaclen::graphics::device::Device;
 
-use gaclen::graphics::buffer::{BufferUsage, CpuAccessibleBuffer};
+use gaclen::graphics::buffer::CpuAccessibleBuffer;
 
 use std::sync::Arc;
 
@@ -14,7 +14,7 @@ gaclen::graphics::impl_vertex!(Vertex, pos, col);
 
 /// Generate a buffer with quad geometry.
 pub fn generate_quad(device: &Device) -> Arc<CpuAccessibleBuffer<[Vertex]>> {
-	CpuAccessibleBuffer::from_iter(device.logical_device(), BufferUsage::all(), false, [
+	device.create_vertex_buffer([
 		Vertex { pos: [-0.5, 0.5, 0.0 ], col: [ 0.75, 0.75, 0.75, 1.0 ] },
 		Vertex { pos: [ 0.5, 0.5, 0.0 ], col: [ 0.75, 0.75, 0.75, 1.0 ] },
 		Vertex { pos: [ 0.5,-0.5, 0.0 ], col: [ 0.75, 0.75, 0.75, 1.0 ] },
@@ -26,7 +26,7 @@ pub fn generate_quad(device: &Device) -> Arc<CpuAccessibleBuffer<[Vertex]>> {
 
 /// Generate a buffer with cube geometry.
 pub fn generate_cube(device: &Device) -> Arc<CpuAccessibleBuffer<[Vertex]>> {
-	CpuAccessibleBuffer::from_iter(device.logical_device(), BufferUsage::all(), false, [
+	device.create_vertex_buffer([
 			// X-
 			Vertex { pos: [ -0.5, -0.5, -0.5 ], col: [ 0.6, 0.7, 0.7, 1.0 ] },
 			Vertex { pos: [ -0.5,  0.5, -0.5 ], col: [ 0.6, 0.7, 0.7, 1.0 ] },
@@ -24,23 +24,26 @@ vulkano::impl_vertex!(Vertex, position, color);
 
 const SHADOW_TEXTURE_SIDE: u32 = 512;
 
+// Passes drawn every frame, ordered by the frame graph.
+enum ScenePass {
+	Shadow,
+	Albedo,
+}
+
 fn main() {
-	let mut frame_count: u64 = 0;
-	let start_time = std::time::Instant::now();
+	let mut clock = graphics::timing::FrameClock::new();
 
 	let event_loop = EventLoop::new();
-	let window = std::sync::Arc::new(
+	let (window, _context, device, mut swapchain) = graphics::bootstrap(
 		WindowBuilder::new()
 			.with_title("Shadowing example")
 			.with_inner_size(winit::dpi::PhysicalSize::new(1280, 720))
-			.with_min_inner_size(winit::dpi::PhysicalSize::new(1280, 720))
-			.build(&event_loop).unwrap()
-	);
-	
-	let context = graphics::context::Context::new().unwrap();
-	let device = graphics::device::Device::new(&context).unwrap();
+			.with_min_inner_size(winit::dpi::PhysicalSize::new(1280, 720)),
+		&event_loop,
+		graphics::PresentMode::Immediate,
+		graphics::image::Format::D16Unorm,
+	).expect("Failed to initialize graphics!");
 	println!("Initialized device: {:?}", device);
-	let mut swapchain = graphics::swapchain::Swapchain::new(&context, &device, window.clone(), graphics::swapchain::PresentMode::Immediate, graphics::image::Format::D16Unorm).expect("Failed to create swapchain!");
 
 	let shadow_pass = {
 		let vs = shaders::shadow::vertex::Shader::load(&device).unwrap();
@@ -76,47 +79,24 @@ fn main() {
 
 	let mut recreate_swapchain = false;
 
-	let plane_matrix_buffer = graphics::buffer::CpuAccessibleBuffer::from_data(
-		device.logical_device(),
-		graphics::buffer::BufferUsage::all(),
-		false,
+	let plane_matrix_buffer = device.create_uniform_buffer(
 		[
 			[ [ 2.0f32, 0.0, 0.0, 0.0 ], [ 0.0, 2.0, 0.0, 0.0 ], [ 0.0, 0.0, 2.0, 0.0 ], [ 0.0, 0.0, 0.0, 1.0 ] ],
 		]
 	).unwrap();
 
-	let cube_matrix_buffer = graphics::buffer::CpuAccessibleBuffer::from_data(
-		device.logical_device(),
-		graphics::buffer::BufferUsage::all(),
-		false,
+	let cube_matrix_buffer = device.create_uniform_buffer(
 		[
 			[ [ 1.0f32, 0.0, 0.0, 0.0 ], [ 0.0, 1.0, 0.0, 0.0 ], [ 0.0, 0.0, 1.0, 0.0 ], [ 0.0, 0.0, 1.5, 1.0 ] ],
 		]
 	).unwrap();
 
-	let light_matrix_buffer = graphics::buffer::CpuAccessibleBuffer::from_data(
-		device.logical_device(),
-		graphics::buffer::BufferUsage::all(),
-		false,
+	let light_matrix_buffer = device.create_uniform_buffer(
 		generate_shadow_matrix()
 	).unwrap();
 
-	let shadow_image = Arc::new(graphics::image::AttachmentImage::sampled(device.logical_device(), [SHADOW_TEXTURE_SIDE; 2], graphics::image::Format::D32Sfloat).unwrap());
-
-	let shadow_sampler = graphics::image::Sampler::compare(
-		device.logical_device(),
-		graphics::image::Filter::Linear,
-		graphics::image::Filter::Linear,
-		graphics::image::MipmapMode::Nearest,
-		graphics::image::SamplerAddressMode::ClampToBorder(graphics::image::BorderColor::FloatOpaqueWhite),
-		graphics::image::SamplerAddressMode::ClampToBorder(graphics::image::BorderColor::FloatOpaqueWhite),
-		graphics::image::SamplerAddressMode::ClampToBorder(graphics::image::BorderColor::FloatOpaqueWhite),
-		0f32,
-		1f32,
-		0f32,
-		0f32,
-		graphics::image::CompareOp::Greater
-	).unwrap();
+	let shadow_image = graphics::image::create_depth_target(&device, [SHADOW_TEXTURE_SIDE; 2], graphics::image::Format::D32Sfloat).unwrap();
+	let shadow_sampler = graphics::image::create_shadow_sampler(&device, graphics::image::CompareOp::Greater).unwrap();
 
 	let plane_matrix_descriptor = Arc::new(
 		albedo_pass.start_persistent_descriptor_set(0)
@@ -142,6 +122,15 @@ fn main() {
 			.build().unwrap()
 	);
 
+	// The albedo pass samples the shadow map, so the shadow pass is drawn first regardless of the order the passes are added in.
+	let graph = {
+		let mut graph = graphics::graph::FrameGraph::new();
+		let shadow_map = graph.add_resource();
+		graph.add_pass(ScenePass::Albedo, &[shadow_map], &[]);
+		graph.add_pass(ScenePass::Shadow, &[], &[shadow_map]);
+		graph
+	};
+
 	// Wrap the device in a stack-allocated container to allow for temporary ownership.
 	let mut device = Some(device);
 
@@ -157,9 +146,7 @@ fn main() {
 		match event {
 			Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
 				*control_flow = ControlFlow::Exit;
-				let run_duration = start_time.elapsed().as_secs_f64();
-				let fps: f64 = frame_count as f64 / run_duration;
-				println!("Produced {} frames over {:.2} seconds ({:.2} avg fps)", frame_count, run_duration, fps);
+				println!("Produced {} frames over {:.2} seconds ({:.2} avg fps)", clock.total_frames(), clock.elapsed().as_secs_f64(), clock.average_fps());
 				return;
 			},
 			Event::WindowEvent { event: WindowEvent::Resized(_), .. } => recreate_swapchain = true,
@@ -197,6 +184,8 @@ fn main() {
 					// Sometimes the swapchain fails to create :(
 					match swapchain.resize(dimensions.into()) {
 						Ok(()) => (),
+						// There is nothing to draw to until the window is restored.
+						Err(graphics::ResizeError::Minimized) => return,
 						Err(graphics::ResizeError::Swapchain(_)) => {
 							println!("Failed to resize window, skipping frame!");
 							return;
@@ -217,25 +206,26 @@ fn main() {
 
 				let albedo_framebuffer = Arc::new(albedo_pass.start_framebuffer()
 					.add(swapchain.get_color_image_for(&frame)).unwrap()
-					.add(swapchain.get_depth_image_for(&frame)).unwrap()
+					.add(swapchain.get_depth_image_for(&frame).expect("The swapchain was created without depth!")).unwrap()
 					.build().unwrap()
 				);
 
 				let camera_matrix = generate_camera_matrix(camera_rotation, window.inner_size().into());
 				
-				let shadow_viewport = graphics::frame::Viewport{ origin: [0f32; 2], dimensions: [SHADOW_TEXTURE_SIDE as f32; 2], depth_range: 0f32..1f32 };
+				// The shadow pass draws to a smaller framebuffer than the window, each pass uses only its own viewport.
+				let shadow_viewport = graphics::frame::viewport_for_framebuffer(&shadow_framebuffer);
 
-				let after_frame = {
-					frame
-						.begin_pass(&shadow_pass, shadow_framebuffer, shadow_viewport, vec![1f32.into()])
+				let after_frame = graph.execute(frame, |frame, pass| match pass {
+					ScenePass::Shadow => frame
+						.begin_pass(&shadow_pass, shadow_framebuffer.clone(), shadow_viewport.clone(), vec![1f32.into()])
 							.draw(vec![cube.clone()], (cube_matrix_descriptor.clone(), light_matrix_descriptor.clone()), ())
-						.finish_pass()
-						.begin_pass(&albedo_pass, albedo_framebuffer, swapchain.default_viewport(), vec![clear_color.into(), 1f32.into()])
+						.finish_pass(),
+					ScenePass::Albedo => frame
+						.begin_pass(&albedo_pass, albedo_framebuffer.clone(), swapchain.default_viewport(), vec![clear_color.into(), 1f32.into()])
 							.draw(vec![quad.clone()], (plane_matrix_descriptor.clone(), light_matrix_descriptor.clone(), shadow_descriptor.clone()), camera_matrix)
 							.draw(vec![cube.clone()], (cube_matrix_descriptor.clone(), light_matrix_descriptor.clone(), shadow_descriptor.clone()), camera_matrix)
-						.finish_pass()
-					.finish()
-				};
+						.finish_pass(),
+				}).finish();
 				
 				device = match after_frame {
 					Ok(device) => Some(device),
@@ -245,7 +235,7 @@ fn main() {
 					},
 				};
 
-				frame_count += 1;
+				clock.tick();
 			}
 			_ => ()
 		};
@@ -253,7 +243,7 @@ fn main() {
 }
 
 
-fn generate_camera_matrix(rotation: cgmath::Quaternion<f32>, viewport_dimensions: (u32, u32)) -> shaders::albedo::vertex::ty::PushConstantData {
+fn generate_camera_matrix(rotation: cgmath::Quaternion<f32>, viewport_dimensions: (u32, u32)) -> shaders::CameraPushConstants {
 	let aspect = viewport_dimensions.0 as f32 / viewport_dimensions.1 as f32;
 
 	let proj: cgmath::Matrix4<f32> = cgmath::PerspectiveFov { fovy: cgmath::Deg(50.0).into(), aspect, near: 1.0, far: 9.0 }.into();
@@ -262,7 +252,7 @@ fn generate_camera_matrix(rotation: cgmath::Quaternion<f32>, viewport_dimensions
 		cgmath::Point3 { x: 0.0, y: 0.0, z: 1.0 },
 		cgmath::Vector3 { x: 0.0, y: 0.0, z: -1.0 });
 
-	shaders::albedo::vertex::ty::PushConstantData {
+	shaders::CameraPushConstants {
 		view_projection_matrix: (proj * view).into()
 	}
 }
@@ -1,3 +1,10 @@
+/// Push constants of passes drawing from the point of view of the camera.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct CameraPushConstants {
+	pub view_projection_matrix: [[f32; 4]; 4],
+}
+
 pub mod shadow {
 	pub mod vertex {
 		gaclen_shader::shader!{
@@ -17,6 +24,7 @@ pub mod albedo {
 		gaclen_shader::shader!{
 			ty: "vertex",
 			path: "examples/shadow/albedo.vert",
+			push_constants: "super::super::CameraPushConstants",
 		}
 	}
 	pub mod fragment {
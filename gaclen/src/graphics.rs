@@ -3,25 +3,99 @@
 //! This is a major part of [gaclen](index.html), since rendering is exclusive to clients.
 //! 
 //! The graphical workflow is extensive, please refer to [examples](https://github.com/Griffone/gaclen/tree/master/examples) for help.
+//! The commonly used types can be imported at once from the [`prelude`](prelude/index.html).
 
 pub mod buffer;
+pub mod camera;
+pub mod color;
 pub mod context;
 pub mod device;
+pub mod format;
 pub mod frame;
+pub mod geometry;
+pub mod graph;
 pub mod image;
+pub mod mesh;
 pub mod pass;
+pub mod prelude;
+pub mod render_target;
 pub mod swapchain;
+pub mod sync;
+pub mod timing;
+pub mod transform;
+pub mod vertex;
 
 /// used for hardware acceleration.
 pub use vulkano;
 pub use vulkano::impl_vertex;
 pub use vulkano::instance::Version;
+pub use vulkano::swapchain::PresentMode;
+
+use context::Context;
+use device::{Device, DeviceCreationError};
+use format::Format;
+use swapchain::{Swapchain, SwapchainCreationError};
+
+use vulkano::instance::InstanceCreationError;
+use winit::event_loop::EventLoop;
+use winit::window::{Window, WindowBuilder};
+
+use std::sync::Arc;
 
 const REQUIRED_VULKAN_VERSION: Version = Version { major: 1, minor: 0, patch: 0 };
 const ENGINE_NAME: &str = "gaclen";
 // Graphical engine version. Is allowed to differ from gaclen cargo version.
 const ENGINE_VERSION: Version = Version { major: 0, minor: 0, patch: 0 };
 
+/// Human-readable description of a [`PresentMode`](enum.PresentMode.html).
+pub trait PresentModeExt {
+	/// Get a short description of the present mode, suitable for settings menus.
+	fn describe(&self) -> &'static str;
+}
+
+impl PresentModeExt for PresentMode {
+	fn describe(&self) -> &'static str {
+		match self {
+			PresentMode::Immediate => "Uncapped",
+			PresentMode::Mailbox => "Triple-buffered",
+			PresentMode::Fifo => "VSync",
+			PresentMode::Relaxed => "Adaptive VSync",
+		}
+	}
+}
+
+/// Create a window along with everything required to draw to it.
+/// 
+/// Uses default [`Context`](context/struct.Context.html), [`Device`](device/struct.Device.html) and a [`Swapchain`](swapchain/struct.Swapchain.html) without inverse depth.
+/// If `depth_format` is `None` the swapchain will not have depth images.
+/// Create each of them individually for more control.
+pub fn bootstrap<T: 'static>(
+	window_builder: WindowBuilder,
+	event_loop: &EventLoop<T>,
+	present_mode: PresentMode,
+	depth_format: impl Into<Option<Format>>,
+) -> Result<(Arc<Window>, Context, Device, Swapchain), BootstrapError>
+{
+	let window = Arc::new(window_builder.build(event_loop)?);
+	let context = Context::new()?;
+	let device = Device::new(&context)?;
+	let swapchain = Swapchain::new(&context, &device, window.clone(), present_mode, depth_format, false)?;
+	Ok((window, context, device, swapchain))
+}
+
+/// Error during [`bootstrap()`](fn.bootstrap.html).
+#[derive(Debug)]
+pub enum BootstrapError {
+	/// Error during creation of the window.
+	Window(winit::error::OsError),
+	/// Error during creation of the [`Context`](context/struct.Context.html).
+	Context(InstanceCreationError),
+	/// Error during creation of the [`Device`](device/struct.Device.html).
+	Device(DeviceCreationError),
+	/// Error during creation of the [`Swapchain`](swapchain/struct.Swapchain.html).
+	Swapchain(SwapchainCreationError),
+}
+
 /// Error during resizing of viewports.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ResizeError {
@@ -29,8 +103,10 @@ pub enum ResizeError {
 	Swapchain(vulkano::swapchain::SwapchainCreationError),
 	/// Error during recreation of depth image of the [`Swapchain`](struct.Swapchain.html).
 	Image(vulkano::image::ImageCreationError),
-	/// The window provided has no apparent size.
-	UnsizedWindow,
+	/// The requested dimensions are zero, typically because the window is minimized.
+	/// 
+	/// The swapchain is left unchanged, drawing should be paused until the window is restored and resized again.
+	Minimized,
 }
 
 impl From<vulkano::swapchain::SwapchainCreationError> for ResizeError {
@@ -39,3 +115,56 @@ impl From<vulkano::swapchain::SwapchainCreationError> for ResizeError {
 impl From<vulkano::image::ImageCreationError> for ResizeError {
 	fn from(err: vulkano::image::ImageCreationError) -> ResizeError { ResizeError::Image(err) }
 }
+
+impl From<winit::error::OsError> for BootstrapError {
+	fn from(err: winit::error::OsError) -> BootstrapError { BootstrapError::Window(err) }
+}
+impl From<InstanceCreationError> for BootstrapError {
+	fn from(err: InstanceCreationError) -> BootstrapError { BootstrapError::Context(err) }
+}
+impl From<DeviceCreationError> for BootstrapError {
+	fn from(err: DeviceCreationError) -> BootstrapError { BootstrapError::Device(err) }
+}
+impl From<SwapchainCreationError> for BootstrapError {
+	fn from(err: SwapchainCreationError) -> BootstrapError { BootstrapError::Swapchain(err) }
+}
+
+impl std::fmt::Display for BootstrapError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			BootstrapError::Window(err) => write!(fmt, "failed to create the window: {}", err),
+			BootstrapError::Context(err) => write!(fmt, "failed to create the context: {}", err),
+			BootstrapError::Device(err) => write!(fmt, "failed to create the device: {}", err),
+			BootstrapError::Swapchain(err) => write!(fmt, "failed to create the swapchain: {}", err),
+		}
+	}
+}
+impl std::error::Error for BootstrapError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			BootstrapError::Window(err) => Some(err),
+			BootstrapError::Context(err) => Some(err),
+			BootstrapError::Device(err) => Some(err),
+			BootstrapError::Swapchain(err) => Some(err),
+		}
+	}
+}
+
+impl std::fmt::Display for ResizeError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			ResizeError::Swapchain(err) => write!(fmt, "failed to recreate the swapchain: {}", err),
+			ResizeError::Image(err) => write!(fmt, "failed to recreate the depth images: {}", err),
+			ResizeError::Minimized => write!(fmt, "the window has no size, it is likely minimized"),
+		}
+	}
+}
+impl std::error::Error for ResizeError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			ResizeError::Swapchain(err) => Some(err),
+			ResizeError::Image(err) => Some(err),
+			ResizeError::Minimized => None,
+		}
+	}
+}
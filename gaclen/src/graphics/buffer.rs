@@ -16,6 +16,16 @@
 //! // buffer is ready to use.
 //! ```
 //! 
+//! [`CpuBufferPool`](struct.CpuBufferPool.html) is best suited for data that changes every frame, use the helpers to create one with appropriate usage:
+//! ```
+//! let device : gaclen::graphics::Device;
+//! let pool = gaclen::graphics::buffer::create_uniform_pool_with_capacity::<[[f32; 4]; 4]>(&device, 3).unwrap();
+//! let buffer = pool.next([[0.0; 4]; 4]).unwrap();
+//! // buffer is ready to use.
+//! ```
+//! Note that [`CpuBufferPool::next()`](struct.CpuBufferPool.html#method.next) allocates new memory if the capacity of the pool is exceeded.
+//! Reserving enough capacity upfront (typically the number of frames in flight times number of uses per frame) avoids allocations mid-frame.
+//! 
 //! Device-local buffers (currently only [`ImmutableBuffer`](struct.ImmutableBuffer.html)) require additional initialization (uploading data to the GPU) and can thus be created through helper methods:
 //! ```
 //! let data : Sized + Send + Sync + 'static;
@@ -24,21 +34,49 @@
 //! let buffer = gaclen::graphics::buffer::create_immutable_buffer_from_data(device, data, usage);
 //! // buffer is ready to use.
 //! ```
+//! 
+//! # Queue families.
+//! 
+//! The [`Device`](../device/struct.Device.html) may upload data on a transfer queue of a different family than the graphics queue that uses it.
+//! Device-local buffers created by the helpers are shared between all queue families of the device (concurrent sharing mode), so no ownership transfer is required.
+//! The uploads finish before the helpers return, so the buffers can be used on any queue right away.
 
-use super::device::Device;
+use super::device::{Device, QueueOperation, SubmitError};
 
 use std::sync::Arc;
 
 use vulkano::buffer::{TypedBufferAccess};
-use vulkano::command_buffer::AutoCommandBufferBuilder;
-use vulkano::sync::GpuFuture;
 use vulkano::memory::DeviceMemoryAllocError;
 
 pub use vulkano::buffer::{BufferAccess, BufferSlice, BufferUsage, CpuAccessibleBuffer, CpuBufferPool, DeviceLocalBuffer, ImmutableBuffer};
 
+/// Maximum number of bytes that can be written with [`update()`](fn.update.html) or [`update_region()`](fn.update_region.html) at once.
+pub const MAX_UPDATE_SIZE: usize = 65536;
+
+/// Error during [updating a region](fn.update_region.html) of a buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UpdateRegionError {
+	/// The region does not fit in the buffer.
+	OutOfBounds {
+		/// Index of the element past the end of the region.
+		end: usize,
+		/// Number of elements in the buffer.
+		len: usize,
+	},
+	/// The data is larger than [`MAX_UPDATE_SIZE`](constant.MAX_UPDATE_SIZE.html).
+	///
+	/// Contains the size of the data in bytes.
+	TooLarge(usize),
+	/// The size of the data is not a whole number of buffer elements, or the offset or size in bytes is not a multiple of 4.
+	Misaligned,
+	/// Error during submission of the update commands.
+	Submit(SubmitError),
+}
+
 /// Create a device-local immutable buffer from some data.
 /// 
-/// Builds an intermediate memory-mapped buffer, writes data to it, builds a copy (upload) command buffer and executes it.
+/// Builds an intermediate memory-mapped buffer, writes data to it, builds a copy (upload) command buffer and executes it on the transfer queue, blocking until it is finished.
+/// The buffer is shared between the queue families of the device, see [queue families](index.html#queue-families).
 /// 
 /// # Panic.
 /// 
@@ -47,17 +85,19 @@ pub fn create_immutable_buffer_from_data<T>(device: &Device, data: T, usage: Buf
 where
 	T : Send + Sync + Sized + 'static,
 {
-	let (buffer, future) = ImmutableBuffer::from_data(data, usage, device.transfer_queue.clone())?;
-
-	// TODO: handle synchronization between separate queues in a performant way
-	future.flush().unwrap();
+	let source = CpuAccessibleBuffer::from_data(device.logical_device(), BufferUsage::transfer_source(), false, data)?;
+	let usage = BufferUsage { transfer_destination: true, .. usage };
+	// Safe, since the buffer is initialized before it is returned.
+	let (buffer, initialization) = unsafe { ImmutableBuffer::<T>::raw(device.logical_device(), std::mem::size_of::<T>(), usage, device.sharing_families())? };
+	device.upload_blocking(|builder| builder.copy_buffer(source, initialization).unwrap());
 
 	Ok(buffer)
 }
 
 /// Create a device-local immutable buffer from some data iterator.
 /// 
-/// Builds an intermediate memory-mapped buffer, writes data to it, builds a copy (upload) command buffer and executes it.
+/// Builds an intermediate memory-mapped buffer, writes data to it, builds a copy (upload) command buffer and executes it on the transfer queue, blocking until it is finished.
+/// The buffer is shared between the queue families of the device, see [queue families](index.html#queue-families).
 /// 
 /// # Panic.
 /// 
@@ -66,29 +106,92 @@ pub fn create_immutable_buffer_from_iter<T>(device: &Device, data_iterator: impl
 where
 	T : Send + Sync + Sized + 'static,
 {
-	let (buffer, future) = ImmutableBuffer::from_iter(data_iterator, usage, device.transfer_queue.clone())?;
-
-	// TODO: handle synchronization between separate queues in a performant way
-	future.flush().unwrap();
+	let len = data_iterator.len();
+	let source = CpuAccessibleBuffer::from_iter(device.logical_device(), BufferUsage::transfer_source(), false, data_iterator)?;
+	let usage = BufferUsage { transfer_destination: true, .. usage };
+	// Safe, since the buffer is initialized before it is returned.
+	let (buffer, initialization) = unsafe { ImmutableBuffer::<[T]>::raw(device.logical_device(), len * std::mem::size_of::<T>(), usage, device.sharing_families())? };
+	device.upload_blocking(|builder| builder.copy_buffer(source, initialization).unwrap());
 
 	Ok(buffer)
 }
 
+/// Create a device-local immutable vertex buffer, the preferred way of storing static geometry.
+/// 
+/// Same as [`create_immutable_buffer_from_iter()`](fn.create_immutable_buffer_from_iter.html) with `BufferUsage::vertex_buffer()`.
+/// 
+/// # Panic.
+/// 
+/// - Panics if fails to submit the copy command buffer.
+#[inline]
+pub fn create_immutable_vertex_buffer<V>(device: &Device, vertices: impl ExactSizeIterator<Item = V>) -> Result<Arc<ImmutableBuffer<[V]>>, DeviceMemoryAllocError>
+where
+	V : Send + Sync + Sized + 'static,
+{
+	create_immutable_buffer_from_iter(device, vertices, BufferUsage::vertex_buffer())
+}
+
+/// Create a device-local immutable index buffer, to be used with a [vertex buffer](fn.create_immutable_vertex_buffer.html) of static geometry.
+/// 
+/// Same as [`create_immutable_buffer_from_iter()`](fn.create_immutable_buffer_from_iter.html) with `BufferUsage::index_buffer()`.
+/// 
+/// # Panic.
+/// 
+/// - Panics if fails to submit the copy command buffer.
+#[inline]
+pub fn create_immutable_index_buffer<I>(device: &Device, indices: impl ExactSizeIterator<Item = I>) -> Result<Arc<ImmutableBuffer<[I]>>, DeviceMemoryAllocError>
+where
+	I : Send + Sync + Sized + 'static,
+{
+	create_immutable_buffer_from_iter(device, indices, BufferUsage::index_buffer())
+}
+
 /// Create an uninitialized device-local buffer for sized data.
 #[inline]
 pub fn create_device_local_buffer<T>(device: &Device, usage: BufferUsage) -> Result<Arc<DeviceLocalBuffer<T>>, DeviceMemoryAllocError> {
-	DeviceLocalBuffer::new(device.logical_device(), usage, device.device.active_queue_families())
+	DeviceLocalBuffer::new(device.logical_device(), usage, device.sharing_families())
 }
 
 /// Create an uninitialized device-local buffer for an array of data.
 #[inline]
 pub fn create_device_local_array_buffer<T>(device: &Device, len: usize, usage: BufferUsage) -> Result<Arc<DeviceLocalBuffer<[T]>>, DeviceMemoryAllocError> {
-	DeviceLocalBuffer::array(device.logical_device(), len, usage, device.device.active_queue_families())
+	DeviceLocalBuffer::array(device.logical_device(), len, usage, device.sharing_families())
+}
+
+/// Create a pool of uniform buffers, useful for per-frame shader data.
+/// 
+/// Note that [`CpuBufferPool::next()`](struct.CpuBufferPool.html#method.next) allocates memory if the capacity of the pool is exceeded.
+#[inline]
+pub fn create_uniform_pool<T>(device: &Device) -> CpuBufferPool<T> {
+	CpuBufferPool::uniform_buffer(device.logical_device())
+}
+
+/// Create a pool of vertex buffers, useful for per-frame geometry.
+/// 
+/// Note that [`CpuBufferPool::next()`](struct.CpuBufferPool.html#method.next) allocates memory if the capacity of the pool is exceeded.
+#[inline]
+pub fn create_vertex_pool<T>(device: &Device) -> CpuBufferPool<T> {
+	CpuBufferPool::vertex_buffer(device.logical_device())
+}
+
+/// Create a pool of uniform buffers with memory preallocated for `capacity` elements.
+pub fn create_uniform_pool_with_capacity<T>(device: &Device, capacity: usize) -> Result<CpuBufferPool<T>, DeviceMemoryAllocError> {
+	let pool = create_uniform_pool(device);
+	pool.reserve(capacity)?;
+	Ok(pool)
+}
+
+/// Create a pool of vertex buffers with memory preallocated for `capacity` elements.
+pub fn create_vertex_pool_with_capacity<T>(device: &Device, capacity: usize) -> Result<CpuBufferPool<T>, DeviceMemoryAllocError> {
+	let pool = create_vertex_pool(device);
+	pool.reserve(capacity)?;
+	Ok(pool)
 }
 
 /// Write data to a buffer.
 /// 
 /// Builds a command buffer for writing the data to the buffer and executes it.
+/// The data should be at most [`MAX_UPDATE_SIZE`](constant.MAX_UPDATE_SIZE.html) bytes, use [`copy()`](fn.copy.html) from a staging buffer for larger data.
 /// 
 /// # Panic
 /// 
@@ -99,13 +202,32 @@ where
 	B : TypedBufferAccess<Content = D> + Send + Sync + 'static,
 	D : Send + Sync + 'static,
 {
-	let command_buffer = AutoCommandBufferBuilder::primary_one_time_submit(device.logical_device(), device.transfer_queue.family()).unwrap()
-		.update_buffer(buffer, data).unwrap()
-		.build().unwrap();
-	
-	vulkano::sync::now(device.logical_device())
-		.then_execute(device.transfer_queue.clone(), command_buffer).unwrap()
-		.flush().unwrap();
+	device.submit_oneshot(QueueOperation::Transfer, |builder| builder.update_buffer(buffer, data).unwrap()).unwrap();
+}
+
+/// Write data to a part of an array buffer, starting at element `offset`.
+/// 
+/// The data should consist of whole elements of the buffer, such as a single `T` or a `[T; N]`.
+/// Only the written region is transferred, which is cheaper than [updating](fn.update.html) the whole buffer for small edits.
+/// 
+/// The data should be at most [`MAX_UPDATE_SIZE`](constant.MAX_UPDATE_SIZE.html) bytes and both the offset and size in bytes should be multiples of 4,
+/// use [`copy()`](fn.copy.html) from a staging buffer otherwise.
+/// 
+/// # Panic
+/// 
+/// - Panics if fails to create the command buffer.
+pub fn update_region<B, T, D>(device: &Device, buffer: B, offset: usize, data: D) -> Result<(), UpdateRegionError>
+where
+	B : TypedBufferAccess<Content = [T]> + Send + Sync + 'static,
+	T : Send + Sync + 'static,
+	D : Send + Sync + 'static,
+{
+	let count = region_length(buffer.len(), std::mem::size_of::<T>(), offset, std::mem::size_of::<D>())?;
+	let slice = BufferSlice::from_typed_buffer_access(buffer).slice(offset..offset + count).unwrap();
+	// The region was validated to be exactly the size of the data.
+	let slice = unsafe { slice.reinterpret::<D>() };
+	device.submit_oneshot(QueueOperation::Transfer, |builder| builder.update_buffer(slice, data).unwrap())?;
+	Ok(())
 }
 
 /// Copies data from one buffer to another.
@@ -128,11 +250,57 @@ where
 	D : TypedBufferAccess<Content = T> + Send + Sync + 'static,
 	T : ?Sized,
 {
-	let command_buffer = AutoCommandBufferBuilder::primary_one_time_submit(device.logical_device(), device.transfer_queue.family()).unwrap()
-		.copy_buffer(source, destination).unwrap()
-		.build().unwrap();
-	
-	vulkano::sync::now(device.logical_device())
-		.then_execute(device.transfer_queue.clone(), command_buffer).unwrap()
-		.flush().unwrap();
+	device.submit_oneshot(QueueOperation::Transfer, |builder| builder.copy_buffer(source, destination).unwrap()).unwrap();
+}
+
+// Validate a region of `data_size` bytes at element `offset` of a buffer with `len` elements, returning the number of elements in the region.
+fn region_length(len: usize, element_size: usize, offset: usize, data_size: usize) -> Result<usize, UpdateRegionError> {
+	if data_size > MAX_UPDATE_SIZE {
+		return Err(UpdateRegionError::TooLarge(data_size));
+	}
+	if element_size == 0 || data_size == 0 || data_size % element_size != 0 || data_size % 4 != 0 || (offset * element_size) % 4 != 0 {
+		return Err(UpdateRegionError::Misaligned);
+	}
+	let count = data_size / element_size;
+	if offset + count > len {
+		return Err(UpdateRegionError::OutOfBounds { end: offset + count, len });
+	}
+	Ok(count)
+}
+
+impl From<SubmitError> for UpdateRegionError {
+	fn from(err: SubmitError) -> UpdateRegionError { UpdateRegionError::Submit(err) }
+}
+
+impl std::fmt::Display for UpdateRegionError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			UpdateRegionError::OutOfBounds { end, len } => write!(fmt, "the region ends at element {}, but the buffer has {} elements", end, len),
+			UpdateRegionError::TooLarge(size) => write!(fmt, "the data is {} bytes, but at most {} bytes can be updated at once", size, MAX_UPDATE_SIZE),
+			UpdateRegionError::Misaligned => write!(fmt, "the data is not a whole number of elements or is not aligned to 4 bytes"),
+			UpdateRegionError::Submit(err) => write!(fmt, "failed to submit the update: {}", err),
+		}
+	}
+}
+impl std::error::Error for UpdateRegionError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			UpdateRegionError::Submit(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_region_length() {
+		assert_eq!(region_length(16, 4, 2, 8), Ok(2));
+		assert_eq!(region_length(16, 4, 15, 8), Err(UpdateRegionError::OutOfBounds { end: 17, len: 16 }));
+		assert_eq!(region_length(16, 4, 0, 6), Err(UpdateRegionError::Misaligned));
+		assert_eq!(region_length(16, 2, 1, 4), Err(UpdateRegionError::Misaligned));
+		assert_eq!(region_length(1 << 20, 4, 0, MAX_UPDATE_SIZE + 4), Err(UpdateRegionError::TooLarge(MAX_UPDATE_SIZE + 4)));
+	}
 }
@@ -0,0 +1,127 @@
+//! Camera provides the view and projection matrices used for rendering a 3D world.
+//!
+//! The matrices produced target vulkan clip-space directly (Y pointing down, depth in [0 : 1] range),
+//! meaning that the `up` vector does not need to be reversed as it does when using OpenGL-style projections.
+//! Use [`vulkan_projection()`](fn.vulkan_projection.html) directly when not using a [`Camera`](struct.Camera.html).
+//!
+//! # Front faces
+//!
+//! With these projections the image is not mirrored, so triangles keep the winding they have in the world when viewed from the camera.
+//! Meshes with counter-clockwise front faces (such as the ones from the [`geometry`](../geometry/index.html) module) should use the default
+//! [`GraphicalPassBuilder::front_face_counter_clockwise()`](../pass/struct.GraphicalPassBuilder.html#method.front_face_counter_clockwise).
+//! OpenGL-style projections with a reversed `up` mirror the image, which is why such code needs to flip the front face as well.
+
+use cgmath::{Deg, Matrix4, PerspectiveFov, Point3, Rad, Vector3};
+
+/// A perspective camera looking at a target point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+	/// Position of the camera in the world.
+	pub position: Point3<f32>,
+	/// Point the camera is looking at.
+	pub target: Point3<f32>,
+	/// The direction that is up in the world.
+	///
+	/// Default is `+Z`.
+	pub up: Vector3<f32>,
+	/// Vertical field of view.
+	pub fov: Rad<f32>,
+	/// Distance to the near clipping plane.
+	pub near: f32,
+	/// Distance to the far clipping plane.
+	pub far: f32,
+}
+
+impl Camera {
+	/// Create a new camera at given position looking at given target.
+	///
+	/// Uses `+Z` as up, 60 degrees vertical field of view and [0.1 : 100.0] clipping range.
+	pub fn new(position: Point3<f32>, target: Point3<f32>) -> Self {
+		Self {
+			position,
+			target,
+			up: Vector3 { x: 0.0, y: 0.0, z: 1.0 },
+			fov: Deg(60.0).into(),
+			near: 0.1,
+			far: 100.0,
+		}
+	}
+
+	/// Get the view matrix, transforming world-space coordinates into camera-space ones.
+	pub fn view(&self) -> Matrix4<f32> {
+		Matrix4::look_at(self.position, self.target, self.up)
+	}
+
+	/// Get the projection matrix, transforming camera-space coordinates into vulkan clip-space ones.
+	pub fn projection(&self, aspect: f32) -> Matrix4<f32> {
+		vulkan_projection(self.fov, aspect, self.near, self.far)
+	}
+
+	/// Get the combined view-projection matrix, transforming world-space coordinates into vulkan clip-space ones.
+	pub fn view_projection(&self, aspect: f32) -> Matrix4<f32> {
+		self.projection(aspect) * self.view()
+	}
+}
+
+impl Default for Camera {
+	fn default() -> Self { Camera::new(Point3 { x: 0.0, y: -1.0, z: 0.0 }, Point3 { x: 0.0, y: 0.0, z: 0.0 }) }
+}
+
+/// Get a perspective projection matrix, transforming camera-space coordinates into vulkan clip-space ones.
+///
+/// - `fov` is the vertical field of view.
+/// - `aspect` is the width of the viewport divided by its height.
+/// - `near` and `far` are the distances to the clipping planes, which are mapped to `0.0` and `1.0` depth respectively.
+///
+/// Unlike OpenGL-style projections the result has Y pointing down, so the `up` vector of the view should not be reversed.
+pub fn vulkan_projection(fov: impl Into<Rad<f32>>, aspect: f32, near: f32, far: f32) -> Matrix4<f32> {
+	let projection: Matrix4<f32> = PerspectiveFov { fovy: fov.into(), aspect, near, far }.into();
+	vulkan_clip_correction() * projection
+}
+
+// Flips Y and remaps depth from OpenGL [-1 : 1] to vulkan [0 : 1] range.
+fn vulkan_clip_correction() -> Matrix4<f32> {
+	Matrix4::new(
+		1.0,  0.0, 0.0, 0.0,
+		0.0, -1.0, 0.0, 0.0,
+		0.0,  0.0, 0.5, 0.0,
+		0.0,  0.0, 0.5, 1.0,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cgmath::Vector4;
+
+	fn project(camera: &Camera, point: Point3<f32>) -> Vector4<f32> {
+		let clip = camera.view_projection(1.0) * point.to_homogeneous();
+		clip / clip.w
+	}
+
+	#[test]
+	fn test_target_is_centered() {
+		let camera = Camera::new(Point3 { x: 3.0, y: 0.0, z: 0.0 }, Point3 { x: 0.0, y: 0.0, z: 0.0 });
+		let ndc = project(&camera, camera.target);
+		assert!(ndc.x.abs() < 1e-5);
+		assert!(ndc.y.abs() < 1e-5);
+		assert!(ndc.z > 0.0 && ndc.z < 1.0);
+	}
+
+	#[test]
+	fn test_up_is_top_of_screen() {
+		let camera = Camera::new(Point3 { x: 3.0, y: 0.0, z: 0.0 }, Point3 { x: 0.0, y: 0.0, z: 0.0 });
+		let ndc = project(&camera, Point3 { x: 0.0, y: 0.0, z: 0.5 });
+		// Vulkan clip-space Y points down.
+		assert!(ndc.y < 0.0);
+	}
+
+	#[test]
+	fn test_clipping_planes_map_to_depth_range() {
+		let camera = Camera::new(Point3 { x: 0.0, y: 0.0, z: 0.0 }, Point3 { x: 1.0, y: 0.0, z: 0.0 });
+		let near = project(&camera, Point3 { x: camera.near, y: 0.0, z: 0.0 });
+		let far = project(&camera, Point3 { x: camera.far, y: 0.0, z: 0.0 });
+		assert!(near.z.abs() < 1e-4);
+		assert!((far.z - 1.0).abs() < 1e-4);
+	}
+}
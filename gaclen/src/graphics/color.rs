@@ -0,0 +1,100 @@
+//! Color provides helpers for working with colors in different color spaces.
+//!
+//! Colors picked by humans (in image editors, color pickers, etc.) are typically *sRGB*-encoded.
+//! Images with *sRGB* formats expect *linear* values to be written to them and encode them automatically.
+//! Use [`srgb()`](fn.srgb.html) to get a clear value that results in the expected visible color regardless of the target format.
+
+use super::format::is_srgb;
+
+use vulkano::format::{ClearValue, Format};
+
+/// Color space the values of an image are encoded in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+	/// Values are *sRGB*-encoded, used for colors meant to be seen (albedo, UI, sprites).
+	Srgb,
+	/// Values are stored as is, used for data (normals, roughness, masks, etc.).
+	Linear,
+}
+
+impl ColorSpace {
+	/// Get the 4-component 8-bit per component format that decodes values of this color space.
+	pub fn rgba8_format(self) -> Format {
+		match self {
+			ColorSpace::Srgb => Format::R8G8B8A8Srgb,
+			ColorSpace::Linear => Format::R8G8B8A8Unorm,
+		}
+	}
+}
+
+/// Get a clear value that results in provided *sRGB*-encoded color when written to an image of `target` format.
+///
+/// The color is decoded into linear space if the target format is *sRGB* (the hardware will encode it back) and used as is otherwise.
+/// The alpha component is never converted.
+pub fn srgb(color: [f32; 4], target: Format) -> ClearValue {
+	match is_srgb(target) {
+		true => ClearValue::Float([srgb_to_linear(color[0]), srgb_to_linear(color[1]), srgb_to_linear(color[2]), color[3]]),
+		false => ClearValue::Float(color),
+	}
+}
+
+/// Get a clear value from a color in linear space.
+///
+/// The color is used as is, which is correct for *sRGB* and floating-point target formats.
+#[inline]
+pub fn linear(color: [f32; 4]) -> ClearValue { ClearValue::Float(color) }
+
+/// Decode an *sRGB*-encoded color component into linear space.
+pub fn srgb_to_linear(value: f32) -> f32 {
+	if value <= 0.04045 {
+		value / 12.92
+	} else {
+		((value + 0.055) / 1.055).powf(2.4)
+	}
+}
+
+/// Encode a linear color component into *sRGB* space.
+pub fn linear_to_srgb(value: f32) -> f32 {
+	if value <= 0.003_130_8 {
+		value * 12.92
+	} else {
+		1.055 * value.powf(1.0 / 2.4) - 0.055
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_conversion_roundtrip() {
+		for i in 0..=255 {
+			let value = i as f32 / 255.0;
+			assert!((linear_to_srgb(srgb_to_linear(value)) - value).abs() < 1e-4);
+		};
+	}
+
+	#[test]
+	fn test_rgba8_format() {
+		assert!(is_srgb(ColorSpace::Srgb.rgba8_format()));
+		assert!(!is_srgb(ColorSpace::Linear.rgba8_format()));
+	}
+
+	#[test]
+	fn test_srgb_clear_value() {
+		let color = [0.5, 0.25, 1.0, 0.5];
+		match srgb(color, Format::B8G8R8A8Unorm) {
+			ClearValue::Float(unconverted) => assert_eq!(unconverted, color),
+			_ => panic!("Expected a floating-point clear value"),
+		}
+		match srgb(color, Format::B8G8R8A8Srgb) {
+			ClearValue::Float(converted) => {
+				assert!(converted[0] < color[0]);
+				assert!(converted[1] < color[1]);
+				assert!((converted[2] - 1.0).abs() < 1e-5);
+				assert_eq!(converted[3], color[3]);
+			},
+			_ => panic!("Expected a floating-point clear value"),
+		}
+	}
+}
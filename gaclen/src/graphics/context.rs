@@ -24,9 +24,50 @@ impl Context {
 	/// Create a new instance of Context with an application name and version.
 	/// 
 	/// This will allow for potential driver-side optimizations specific to your application.
+	/// 
+	/// Use [`context_with_cargo_app_info!()`](../../macro.context_with_cargo_app_info.html) to use the name and version of the calling crate.
 	pub fn with_app_info(name: &str, version: Version) -> Result<Context, InstanceCreationError> { Context::create(Some(name), Some(version), vulkano_win::required_extensions()) }
 
 	// TODO: add a version with custom extensions
+
+	/// Check whether swapchains can use color spaces other than *sRGB* nonlinear, such as HDR ones.
+	/// 
+	/// The `VK_EXT_swapchain_colorspace` extension is enabled automatically if supported.
+	/// The actually available color spaces depend on the display, see [`Swapchain::new_with()`](../swapchain/struct.Swapchain.html#method.new_with).
+	#[inline]
+	pub fn supports_extended_color_spaces(&self) -> bool { self.instance.loaded_extensions().ext_swapchain_colorspace }
+}
+
+/// Parse a [`Version`](../struct.Version.html) from its components, such as the ones Cargo provides in `CARGO_PKG_VERSION_*` variables.
+/// 
+/// # Panic.
+/// 
+/// - Panics if any of the components is not a number that fits into `u16`.
+pub fn parse_version(major: &str, minor: &str, patch: &str) -> Version {
+	let parse = |component: &str| component.parse::<u16>().unwrap_or_else(|_| panic!("{:?} is not a valid version component", component));
+	Version { major: parse(major), minor: parse(minor), patch: parse(patch) }
+}
+
+/// Get the [`Version`](graphics/struct.Version.html) of the calling crate, as specified in its `Cargo.toml`.
+#[macro_export]
+macro_rules! cargo_app_version {
+	() => {
+		$crate::graphics::context::parse_version(
+			env!("CARGO_PKG_VERSION_MAJOR"),
+			env!("CARGO_PKG_VERSION_MINOR"),
+			env!("CARGO_PKG_VERSION_PATCH"),
+		)
+	};
+}
+
+/// Create a [`Context`](graphics/context/struct.Context.html) with the name and version of the calling crate as the application info.
+/// 
+/// Same as [`Context::with_app_info()`](graphics/context/struct.Context.html#method.with_app_info) with the values from the `Cargo.toml` of the calling crate, which allows the driver to match application profiles.
+#[macro_export]
+macro_rules! context_with_cargo_app_info {
+	() => {
+		$crate::graphics::context::Context::with_app_info(env!("CARGO_PKG_NAME"), $crate::cargo_app_version!())
+	};
 }
 
 #[cfg(feature = "expose-underlying-vulkano")]
@@ -40,8 +81,12 @@ impl Context {
 	fn create(
 		application_name: Option<&str>,
 		application_version: Option<Version>,
-		extensions: InstanceExtensions
+		mut extensions: InstanceExtensions
 	) -> Result<Context, InstanceCreationError> {
+		if let Ok(supported) = InstanceExtensions::supported_by_core() {
+			extensions.ext_swapchain_colorspace = supported.ext_swapchain_colorspace;
+		}
+
 		let application_name: Option<Cow<str>> = match application_name {
 			Some(name) => Some(Cow::from(name)),
 			None => None,
@@ -53,6 +98,19 @@ impl Context {
 			engine_version: Some(ENGINE_VERSION),
 		};
 		let instance = Instance::new(Some(&app_info), &extensions, None)?;
+		log_event!(debug, "gaclen: created instance with extensions {:?}", instance.loaded_extensions());
 		Ok(Context { instance })
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_cargo_app_version() {
+		let version = cargo_app_version!();
+		assert_eq!(version, parse_version(env!("CARGO_PKG_VERSION_MAJOR"), env!("CARGO_PKG_VERSION_MINOR"), env!("CARGO_PKG_VERSION_PATCH")));
+		assert_eq!(parse_version("1", "2", "3"), Version { major: 1, minor: 2, patch: 3 });
+	}
+}
@@ -4,16 +4,29 @@ use super::context::Context;
 
 use std::sync::Arc;
 
+use vulkano::buffer::{BufferUsage, CpuAccessibleBuffer};
+use vulkano::command_buffer::{AutoCommandBuffer, AutoCommandBufferBuilder, CommandBufferExecError, CommandBufferExecFuture};
 use vulkano::device::{Device as LogicalDevice, DeviceExtensions, Queue as DeviceQueue};
-use vulkano::instance::PhysicalDevice;
-use vulkano::sync::{GpuFuture};
-
-pub use vulkano::swapchain::PresentMode;
+use vulkano::format::{AcceptsPixels, Format, FormatDesc};
+use vulkano::image::{Dimensions, ImageCreationError, ImmutableImage};
+use vulkano::instance::{PhysicalDevice, QueueFamily};
+use vulkano::memory::DeviceMemoryAllocError;
+use vulkano::sync::{FenceSignalFuture, FlushError, GpuFuture};
 
 /// A device responsible for hardware-accelerated computations.
 /// 
 /// It is responsible for recording, submitting and synchronizing commands and data to the GPU.
 /// The device structure contains some state information for synchronization purposes.
+/// 
+/// The device may use separate queues from different queue families for graphics, transfer and compute operations.
+/// Resources created by **gaclen** helpers are shared between all used queue families (concurrent sharing mode), so they don't require ownership transfers.
+/// Resources created with vulkano directly should be created with the same families, see [`Device::logical_device()`](#method.logical_device).
+/// 
+/// A single device can draw to any number of [`Swapchains`](../swapchain/struct.Swapchain.html) (for example one per window).
+/// Frames are drawn one after another, each frame waits for the previous one on the graphics queue, regardless of the swapchain it is presented to.
+/// 
+/// The device should not be dropped while GPU work is in flight, call [`wait_for_idle()`](#method.wait_for_idle) before dropping it.
+/// With the `strict-validation` feature dropping a device waits for the in-flight work to finish, logging a warning if the last frame was not finished yet (the feature enables `logging`).
 pub struct Device {
 	pub(super) device: Arc<LogicalDevice>,
 
@@ -22,6 +35,88 @@ pub struct Device {
 	pub(super) compute_queue: Arc<DeviceQueue>,
 
 	pub(super) before_frame: Option<Box<dyn GpuFuture>>,
+	#[cfg(feature = "strict-validation")]
+	pub(super) frame_fence: Option<Arc<FrameFence>>,
+}
+
+// The future signaled once the GPU finished drawing (and presenting) a frame.
+pub(super) type FrameFence = FenceSignalFuture<Box<dyn GpuFuture>>;
+
+/// Configuration used for creating a [`Device`](struct.Device.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceConfig {
+	/// Policy for requesting queues from the device.
+	/// 
+	/// Default is [`QueuePolicy::Separate`](enum.QueuePolicy.html#variant.Separate) with both transfer and compute queues.
+	pub queue_policy: QueuePolicy,
+	/// Priorities of the requested queues.
+	pub queue_priorities: QueuePriorities,
+	/// Create the device without presentation support, only requiring compute capabilities.
+	/// 
+	/// A headless device can not be used with a [`Swapchain`](../swapchain/struct.Swapchain.html).
+	/// Default is `false`.
+	pub headless: bool,
+	/// Optional features the device is required to support.
+	/// 
+	/// All features supported by the selected device are enabled, this guarantees the required ones are among them.
+	/// Device creation fails with [`DeviceCreationError::UnsupportedFeatures`](enum.DeviceCreationError.html#variant.UnsupportedFeatures) otherwise.
+	/// Default is no required features.
+	pub required_features: DeviceFeatures,
+	/// Require the device to support compute operations.
+	/// 
+	/// Rendering-only clients can disable it to allow the rare devices without compute support, in which case the compute queue is the graphics one and [`Device::supports_compute()`](struct.Device.html#method.supports_compute) should be checked before dispatching.
+	/// Headless devices always require compute support.
+	/// Default is `true`.
+	pub require_compute: bool,
+}
+
+/// Policy for requesting queues from a device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueuePolicy {
+	/// Use a single queue for all operations.
+	SingleQueue,
+	/// Try to use separate queues (preferably from dedicated families) for given operations.
+	/// 
+	/// Operations that don't get a separate queue share the graphics one.
+	Separate {
+		/// Request a separate queue for transfer operations.
+		transfer: bool,
+		/// Request a separate queue for compute operations.
+		compute: bool,
+	},
+}
+
+/// Priorities of queues requested from a device, in [0.0 : 1.0] range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QueuePriorities {
+	/// Priority of the graphics queue, default is `1.0`.
+	pub graphics: f32,
+	/// Priority of the transfer queue (if separate), default is `0.5`.
+	pub transfer: f32,
+	/// Priority of the compute queue (if separate), default is `0.25`.
+	pub compute: f32,
+}
+
+/// Indices of the queue families used by a [`Device`](struct.Device.html) for each [operation](enum.QueueOperation.html).
+/// 
+/// Multiple operations may use the same family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueueFamilyIndices {
+	/// Family of the queue used for drawing and presenting.
+	pub graphics: u32,
+	/// Family of the queue used for uploads.
+	pub transfer: u32,
+	/// Family of the queue used for compute dispatches.
+	pub compute: u32,
+}
+
+/// A thread-safe handle for recording commands for a [`Device`](struct.Device.html) on other threads.
+/// 
+/// Used for recording [`SecondaryCommands`](../frame/struct.SecondaryCommands.html).
+#[derive(Clone)]
+pub struct CommandContext {
+	pub(super) device: Arc<LogicalDevice>,
+	pub(super) graphics_queue: Arc<DeviceQueue>,
 }
 
 /// Error during device creation.
@@ -31,22 +126,123 @@ pub enum DeviceCreationError {
 	NoPhysicalDevicesFound,
 	/// Some hardware devices was found, but none of it was applicable for gaclen.
 	NoCompatiblePhysicalDeviceFound,
+	/// A hardware device was found, but it does not support compute operations, which are [required](struct.DeviceConfig.html#structfield.require_compute).
+	ComputeNotSupported,
 	/// Error during the creation of logical device.
 	Logical(vulkano::device::DeviceCreationError),
+	/// None of the created queues is able to perform given operation.
+	QueueCapabilityMismatch(QueueOperation),
+	/// The selected device does not support some of the [required features](struct.DeviceConfig.html#structfield.required_features).
+	/// 
+	/// Contains the required features that are not supported.
+	UnsupportedFeatures(DeviceFeatures),
+}
+
+/// Availability of optional device features.
+/// 
+/// Can be used to gracefully disable effects on hardware that doesn't support them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeviceFeatures {
+	/// Geometry shaders are supported.
+	pub geometry_shader: bool,
+	/// Tessellation control and evaluation shaders are supported.
+	pub tessellation_shader: bool,
+	/// Lines with width other than `1.0` are supported.
+	pub wide_lines: bool,
+	/// Anisotropic filtering of samplers is supported.
+	pub sampler_anisotropy: bool,
+	/// Polygon modes other than fill (wireframe and point) are supported.
+	pub fill_mode_non_solid: bool,
+	/// Clamping of fragment depth instead of clipping is supported.
+	pub depth_clamp: bool,
+	/// Bitwise logic operations for combining written colors are supported.
+	pub logic_op: bool,
+	/// Shading multisampled attachments per sample instead of per pixel is supported.
+	pub sample_rate_shading: bool,
+}
+
+/// Amount of device-local memory, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryBudget {
+	/// Total size of device-local memory heaps.
+	pub total: u64,
+	/// Currently used device-local memory, if the device is able to report it.
+	pub used: Option<u64>,
+}
+
+/// Error during submission of one-shot commands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitError {
+	/// Error during attempted execution of GPU commands.
+	Commands(CommandBufferExecError),
+	/// Error during flushing commands to the GPU.
+	Flush(FlushError),
+}
+
+// Sharing mode of resources used by queues of given families.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum FamilySharing {
+	// All queues are from a single family, resources can be exclusive to it.
+	Exclusive(u32),
+	// Queues are from distinct families, resources have to be shared concurrently between them.
+	Concurrent(Vec<u32>),
+}
+
+/// An operation a device queue is used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueueOperation {
+	/// Drawing operations.
+	Graphics,
+	/// Memory transfer operations.
+	Transfer,
+	/// Compute shader operations.
+	Compute,
 }
 
 impl Device {
 	/// Create a new device using provided driver context.
+	/// 
+	/// Uses default [`DeviceConfig`](struct.DeviceConfig.html).
 	pub fn new(
 		context: &Context,
 	) -> Result<Device, DeviceCreationError>
 	{
-		let physical = select_physical_device(context)?;
+		Device::with_config(context, DeviceConfig::default())
+	}
 
-		let device_extensions = DeviceExtensions { khr_swapchain: true, .. DeviceExtensions::none() };
-		let queues = select_queue_families(&physical);
-		let (logical, queues) = LogicalDevice::new(physical, physical.supported_features(), &device_extensions, queues.iter().cloned())?;
-		let [graphics_queue, transfer_queue, compute_queue] = unpack_queues(queues.collect());
+	/// Create a new device without presentation support using provided driver context.
+	/// 
+	/// Useful for compute-only tools and tests that don't have a window.
+	/// Same as using [`DeviceConfig`](struct.DeviceConfig.html) with `headless` set to `true`.
+	pub fn new_headless(
+		context: &Context,
+	) -> Result<Device, DeviceCreationError>
+	{
+		Device::with_config(context, DeviceConfig { headless: true, .. DeviceConfig::default() })
+	}
+
+	/// Create a new device using provided driver context and configuration.
+	pub fn with_config(
+		context: &Context,
+		config: DeviceConfig,
+	) -> Result<Device, DeviceCreationError>
+	{
+		let physical = select_physical_device(context, &config)?;
+		if let Some(missing) = missing_features(&config.required_features, &physical.supported_features().into()) {
+			return Err(DeviceCreationError::UnsupportedFeatures(missing));
+		}
+
+		let supported_extensions = DeviceExtensions::supported_by_device(physical);
+		let device_extensions = DeviceExtensions {
+			khr_swapchain: !config.headless,
+			khr_incremental_present: !config.headless && supported_extensions.khr_incremental_present,
+			.. DeviceExtensions::none()
+		};
+		let selection = select_queue_families(&physical, &config);
+		let (logical, queues) = LogicalDevice::new(physical, physical.supported_features(), &device_extensions, selection.families.iter().cloned())?;
+		let [graphics_queue, transfer_queue, compute_queue] = validate_queues(unpack_queues(queues.collect(), &selection), config.headless, requires_compute(&config))?;
+		log_event!(info, "gaclen: selected device {} ({:?})", physical.name(), physical.ty());
+		log_event!(debug, "gaclen: using queue families {} (graphics), {} (transfer), {} (compute)", graphics_queue.family().id(), transfer_queue.family().id(), compute_queue.family().id());
 
 		Ok(Device {
 			device: logical,
@@ -54,43 +250,390 @@ impl Device {
 			transfer_queue,
 			compute_queue,
 			before_frame: None,
+			#[cfg(feature = "strict-validation")]
+			frame_fence: None,
 		})
 	}
 
+	/// Query the optional features supported by the hardware device that would be selected by [`with_config()`](#method.with_config).
+	/// 
+	/// Allows adjusting the configuration before creating the device.
+	pub fn query_features(
+		context: &Context,
+		config: &DeviceConfig,
+	) -> Result<DeviceFeatures, DeviceCreationError>
+	{
+		let physical = select_physical_device(context, config)?;
+		Ok(physical.supported_features().into())
+	}
+
+	/// Get the optional features supported by the device.
+	pub fn supported_features(&self) -> DeviceFeatures { self.device.physical_device().supported_features().into() }
+
+	/// Get the optional features enabled for the device.
+	/// 
+	/// Resources that depend on a feature (for example an anisotropic [`Sampler`](../image/struct.Sampler.html)) can only be created if it is enabled.
+	pub fn enabled_features(&self) -> DeviceFeatures { self.device.enabled_features().into() }
+
+	/// Get the amount of device-local memory, useful for scaling asset quality.
+	/// 
+	/// The total is the sum of sizes of device-local memory heaps.
+	/// Memory usage is currently not reported, since `VK_EXT_memory_budget` is not exposed by vulkano.
+	pub fn memory_budget(&self) -> MemoryBudget {
+		// TODO/vulkano: query usage with VK_EXT_memory_budget when available.
+		let total = self.device.physical_device().memory_heaps()
+			.filter(|heap| heap.is_device_local())
+			.map(|heap| heap.size() as u64)
+			.sum();
+		MemoryBudget { total, used: None }
+	}
+
 	/// Get the PhysicalDevice selected when this Device was created.
 	pub fn physical_device(&self) -> PhysicalDevice {
 		self.device.physical_device()
 	}
 
+	/// Get a thread-safe handle for recording commands on other threads.
+	pub fn command_context(&self) -> CommandContext {
+		CommandContext {
+			device: self.device.clone(),
+			graphics_queue: self.graphics_queue.clone(),
+		}
+	}
+
+	/// Upload an [`ImmutableImage`](../image/struct.ImmutableImage.html) from a data iterator without waiting for the upload to finish.
+	/// 
+	/// The upload is submitted to the transfer queue and the next [`Frame`](../frame/struct.Frame.html) waits for it to finish before drawing.
+	/// Unlike [`create_immutable_image_from_iter()`](../image/fn.create_immutable_image_from_iter.html) this does not block, which allows streaming textures while drawing.
+	/// The image should not be used outside of frames until the next frame is finished.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to submit the copy command buffer.
+	pub fn upload_immutable_image<P, I, F>(&mut self, data_iterator: I, dimensions: Dimensions, format: F)
+	-> Result<Arc<ImmutableImage<F>>, ImageCreationError>
+	where
+		P : Send + Sync + Clone + 'static,
+		F : FormatDesc + AcceptsPixels<P> + Send + Sync + 'static,
+		I : ExactSizeIterator<Item = P>,
+		Format: AcceptsPixels<P>,
+	{
+		let (image, future) = ImmutableImage::from_iter(data_iterator, dimensions, format, self.transfer_queue.clone())?;
+
+		// The semaphore allows the graphics queue to wait for the upload on the transfer queue.
+		let future = future.then_signal_semaphore_and_flush().unwrap();
+
+		let time: Box<dyn GpuFuture> = match self.before_frame.take() {
+			Some(time) => Box::new(time.join(future)),
+			None => Box::new(future),
+		};
+		self.before_frame = Some(time);
+
+		Ok(image)
+	}
+
+	/// Record and submit a command buffer that is executed once, such as for layout transitions, clears or mipmap generation.
+	/// 
+	/// The commands are recorded by `record` and submitted to the queue used for given `operation`.
+	/// The returned future is already flushed, it can be waited on or chained with other GPU work.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to create the command buffer.
+	/// - Panics if fails to build (finalize) the command buffer.
+	pub fn submit_oneshot<F>(&self, operation: QueueOperation, record: F) -> Result<impl GpuFuture, SubmitError>
+	where
+		F : FnOnce(AutoCommandBufferBuilder) -> AutoCommandBufferBuilder,
+	{
+		let future = self.submit_after(vulkano::sync::now(self.logical_device()), operation, record)?;
+		future.flush()?;
+		Ok(future)
+	}
+
+	/// Check whether the device supports compute operations, which is guaranteed unless [`require_compute`](struct.DeviceConfig.html#structfield.require_compute) was disabled.
+	#[inline]
+	pub fn supports_compute(&self) -> bool { supports_operation(&self.compute_queue, QueueOperation::Compute) }
+
+	/// Check whether the device can present only changed regions of frames, see [`Frame::finish_with_regions()`](../frame/struct.Frame.html#method.finish_with_regions).
+	/// 
+	/// The `VK_KHR_incremental_present` extension is enabled automatically if supported.
+	#[inline]
+	pub fn supports_incremental_present(&self) -> bool { self.device.loaded_extensions().khr_incremental_present }
+
+	// TODO/vulkano: naming objects for debugging tools (`Device::set_debug_name()` via VK_EXT_debug_utils, behind `strict-validation`), vulkano 0.18 has no safe access to vkSetDebugUtilsObjectNameEXT or object handles.
+
+	// Record a one-time command buffer and execute it on the queue used for given `operation` after `before`.
+	// The returned future is not flushed, so it can be chained with other work before being submitted to the GPU.
+	pub(super) fn submit_after<B, F>(&self, before: B, operation: QueueOperation, record: F) -> Result<CommandBufferExecFuture<B, AutoCommandBuffer>, SubmitError>
+	where
+		B : GpuFuture,
+		F : FnOnce(AutoCommandBufferBuilder) -> AutoCommandBufferBuilder,
+	{
+		let queue = self.queue(operation);
+		let builder = AutoCommandBufferBuilder::primary_one_time_submit(self.logical_device(), queue.family()).unwrap();
+		let command_buffer = record(builder).build().unwrap();
+
+		Ok(before.then_execute(queue.clone(), command_buffer)?)
+	}
+
+	// Get the queue used for given operation.
+	pub(super) fn queue(&self, operation: QueueOperation) -> &Arc<DeviceQueue> {
+		match operation {
+			QueueOperation::Graphics => &self.graphics_queue,
+			QueueOperation::Transfer => &self.transfer_queue,
+			QueueOperation::Compute => &self.compute_queue,
+		}
+	}
+
+	// Record and submit commands on the transfer queue, blocking until they are finished.
+	// Resources shared between queue families must be created with `sharing_families()` (concurrent sharing).
+	pub(super) fn upload_blocking<F>(&self, record: F)
+	where
+		F : FnOnce(AutoCommandBufferBuilder) -> AutoCommandBufferBuilder,
+	{
+		self.submit_oneshot(QueueOperation::Transfer, record).unwrap()
+			.then_signal_fence_and_flush().unwrap()
+			.wait(None).unwrap();
+	}
+
+	/// Block until all work submitted by the device (drawn frames and uploads) is finished.
+	/// 
+	/// This is not required before [resizing](../swapchain/struct.Swapchain.html#method.resize) a swapchain, but can be used to make sure no resources are in use, for example before releasing large ones.
+	pub fn wait_for_idle(&mut self) -> Result<(), FlushError> {
+		if let Some(time) = self.before_frame.take() {
+			time.then_signal_fence_and_flush()?.wait(None)?;
+		}
+		#[cfg(feature = "strict-validation")]
+		{ self.frame_fence = None; }
+		Ok(())
+	}
+
+	/// Create a host-accessible buffer with provided data.
+	/// 
+	/// Unlike creating a [`CpuAccessibleBuffer`](../buffer/struct.CpuAccessibleBuffer.html) directly, the buffer is shared between all queue families used by the device.
+	pub fn create_buffer<T>(&self, data: T, usage: BufferUsage) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError>
+	where
+		T : Send + Sync + 'static,
+	{
+		let size = std::mem::size_of::<T>();
+		// Safe, since the contents are initialized before the buffer is returned.
+		let buffer = unsafe { CpuAccessibleBuffer::<T>::raw(self.logical_device(), size, usage, false, self.sharing_families())? };
+		{
+			let mut mapping = buffer.write().unwrap();
+			unsafe { std::ptr::write(&mut *mapping, data) };
+		}
+		Ok(buffer)
+	}
+
+	/// Create a host-accessible buffer with an array of provided data.
+	/// 
+	/// Unlike creating a [`CpuAccessibleBuffer`](../buffer/struct.CpuAccessibleBuffer.html) directly, the buffer is shared between all queue families used by the device.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if the iterator yields fewer elements than its reported length.
+	pub fn create_array_buffer<T>(&self, data_iterator: impl ExactSizeIterator<Item = T>, usage: BufferUsage) -> Result<Arc<CpuAccessibleBuffer<[T]>>, DeviceMemoryAllocError>
+	where
+		T : Send + Sync + 'static,
+	{
+		let len = data_iterator.len();
+		// Safe, since the buffer is not returned unless all of its contents are initialized.
+		let buffer = unsafe { CpuAccessibleBuffer::<[T]>::raw(self.logical_device(), len * std::mem::size_of::<T>(), usage, false, self.sharing_families())? };
+		{
+			let mut mapping = buffer.write().unwrap();
+			let mut written = 0;
+			for (element, value) in mapping.iter_mut().zip(data_iterator) {
+				unsafe { std::ptr::write(element, value) };
+				written += 1;
+			};
+			assert_eq!(written, len, "the iterator yielded fewer elements than its reported length");
+		}
+		Ok(buffer)
+	}
+
+	/// Create a host-accessible vertex buffer, useful for quick prototyping or geometry that changes often.
+	/// 
+	/// Same as [`Device::create_array_buffer()`](#method.create_array_buffer) with `BufferUsage::vertex_buffer()`.
+	#[inline]
+	pub fn create_vertex_buffer<V>(&self, vertices: impl ExactSizeIterator<Item = V>) -> Result<Arc<CpuAccessibleBuffer<[V]>>, DeviceMemoryAllocError>
+	where
+		V : Send + Sync + 'static,
+	{
+		self.create_array_buffer(vertices, BufferUsage::vertex_buffer())
+	}
+
+	/// Create a host-accessible index buffer, to be used with a [vertex buffer](#method.create_vertex_buffer).
+	/// 
+	/// Same as [`Device::create_array_buffer()`](#method.create_array_buffer) with `BufferUsage::index_buffer()`.
+	#[inline]
+	pub fn create_index_buffer<I>(&self, indices: impl ExactSizeIterator<Item = I>) -> Result<Arc<CpuAccessibleBuffer<[I]>>, DeviceMemoryAllocError>
+	where
+		I : Send + Sync + 'static,
+	{
+		self.create_array_buffer(indices, BufferUsage::index_buffer())
+	}
+
+	/// Create a host-accessible uniform buffer, useful for shader data that changes rarely.
+	/// 
+	/// Same as [`Device::create_buffer()`](#method.create_buffer) with `BufferUsage::uniform_buffer()`.
+	/// Prefer a [uniform pool](../buffer/fn.create_uniform_pool.html) for data that changes every frame.
+	#[inline]
+	pub fn create_uniform_buffer<T>(&self, data: T) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError>
+	where
+		T : Send + Sync + 'static,
+	{
+		self.create_buffer(data, BufferUsage::uniform_buffer())
+	}
+
 	/// Get the underlying vulkano logical device.
 	/// 
 	/// The result can be useful for creating simple resources that don't require much usage of gaclen's functionality.
 	pub fn logical_device(&self) -> Arc<LogicalDevice> { self.device.clone() }
+
+	/// Get the indices of the queue families used for each operation.
+	pub fn queue_family_indices(&self) -> QueueFamilyIndices {
+		QueueFamilyIndices {
+			graphics: self.graphics_queue.family().id(),
+			transfer: self.transfer_queue.family().id(),
+			compute: self.compute_queue.family().id(),
+		}
+	}
+
+	/// Get the indices of all queue families used by the device, without duplicates.
+	/// 
+	/// Resources created by **gaclen** helpers are shared between these families, resources created directly with vulkano should be too.
+	pub fn active_queue_families(&self) -> Vec<u32> { self.device.active_queue_families().map(|family| family.id()).collect() }
+
+	// Get how resources used by all queues of the device should be shared.
+	pub(super) fn resource_sharing(&self) -> FamilySharing {
+		let indices = self.queue_family_indices();
+		family_sharing(&[indices.graphics, indices.transfer, indices.compute])
+	}
+
+	// Get the queue families resources used by all queues of the device should be created with.
+	pub(super) fn sharing_families(&self) -> Vec<QueueFamily> {
+		let physical = self.device.physical_device();
+		let family = |id: u32| physical.queue_family_by_id(id).expect("The queue family of a created queue should exist.");
+		match self.resource_sharing() {
+			FamilySharing::Exclusive(id) => vec![family(id)],
+			FamilySharing::Concurrent(ids) => ids.into_iter().map(family).collect(),
+		}
+	}
+
+	/// Get the vulkano queue used for graphical operations, such as for submitting custom command buffers.
+	/// 
+	/// Submissions on the queue are not synchronized with the frames of the device, prefer [`submit_oneshot()`](#method.submit_oneshot) where possible.
+	#[inline]
+	pub fn graphics_queue(&self) -> Arc<DeviceQueue> { self.graphics_queue.clone() }
 }
 
 #[cfg(feature = "expose-underlying-vulkano")]
 impl Device {
-	/// Get the [vulkano device queue](struct.DeviceQueue.html) used for graphical operations.
-	#[inline(always)]
-	pub fn graphics_queue(&self) -> &Arc<DeviceQueue> { self.graphics_queue }
 	/// Get the [vulkano device queue](struct.DeviceQueue.html) used for transfer operations.
 	#[inline(always)]
-	pub fn transfer_queue(&self) -> &Arc<DeviceQueue> { self.transfer_queue }
+	pub fn transfer_queue(&self) -> &Arc<DeviceQueue> { &self.transfer_queue }
 	/// Get the [vulkano device queue](struct.DeviceQueue.html) used for compute operations.
 	#[inline(always)]
-	pub fn compute_queue(&self) -> &Arc<DeviceQueue> { self.compute_queue }
-	/// Get the [vulkano swapchian](struct.Swapchain.html) used for presenting images on the screen.
-	#[inline(always)]
-	pub fn swapchain(&self) -> &Arc<Swapchain<Arc<Window>>> { self.swapchain }
-	/// Get the [vulkano swapchain images](struct.SwapchainImage.html) that are presented on the screen.
-	#[inline(always)]
-	pub fn swapchain_images(&self) -> &Vec<Arc<SwapchainImage<Arc<Window>>>> { self.swapchain_images }
+	pub fn compute_queue(&self) -> &Arc<DeviceQueue> { &self.compute_queue }
+}
+
+impl Default for DeviceConfig {
+	fn default() -> Self {
+		Self {
+			queue_policy: QueuePolicy::Separate { transfer: true, compute: true },
+			queue_priorities: QueuePriorities::default(),
+			headless: false,
+			required_features: DeviceFeatures::default(),
+			require_compute: true,
+		}
+	}
+}
+
+impl Default for QueuePriorities {
+	fn default() -> Self { Self { graphics: 1.0, transfer: 0.5, compute: 0.25 } }
+}
+
+impl From<&vulkano::device::Features> for DeviceFeatures {
+	fn from(features: &vulkano::device::Features) -> DeviceFeatures {
+		DeviceFeatures {
+			geometry_shader: features.geometry_shader,
+			tessellation_shader: features.tessellation_shader,
+			wide_lines: features.wide_lines,
+			sampler_anisotropy: features.sampler_anisotropy,
+			fill_mode_non_solid: features.fill_mode_non_solid,
+			depth_clamp: features.depth_clamp,
+			logic_op: features.logic_op,
+			sample_rate_shading: features.sample_rate_shading,
+		}
+	}
 }
 
 impl From<vulkano::device::DeviceCreationError> for DeviceCreationError {
 	fn from(err: vulkano::device::DeviceCreationError) -> DeviceCreationError { DeviceCreationError::Logical(err) }
 }
 
+impl From<CommandBufferExecError> for SubmitError {
+	fn from(err: CommandBufferExecError) -> SubmitError { SubmitError::Commands(err) }
+}
+impl From<FlushError> for SubmitError {
+	fn from(err: FlushError) -> SubmitError { SubmitError::Flush(err) }
+}
+
+#[cfg(feature = "strict-validation")]
+impl Drop for Device {
+	fn drop(&mut self) {
+		// the fence of the last frame is signaled once the GPU finished drawing it
+		let frame_in_flight = self.frame_fence.take().map_or(false, |fence| fence.is_signaled() == Ok(false));
+		if frame_in_flight {
+			log_event!(warn, "gaclen: Device dropped with GPU work in flight, call Device::wait_for_idle() before dropping it. Waiting for the work to finish.");
+		}
+		if let Some(time) = self.before_frame.take() {
+			let waited = time.then_signal_fence_and_flush().and_then(|fence| fence.wait(None));
+			if let Err(err) = waited {
+				log_event!(warn, "gaclen: failed to wait for in-flight GPU work: {}", err);
+			}
+		}
+	}
+}
+
+impl std::fmt::Display for DeviceCreationError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			DeviceCreationError::NoPhysicalDevicesFound => write!(fmt, "no hardware devices were found"),
+			DeviceCreationError::NoCompatiblePhysicalDeviceFound => write!(fmt, "no compatible hardware device was found"),
+			DeviceCreationError::ComputeNotSupported => write!(fmt, "the hardware device does not support compute operations"),
+			DeviceCreationError::Logical(err) => write!(fmt, "failed to create the logical device: {}", err),
+			DeviceCreationError::QueueCapabilityMismatch(operation) => write!(fmt, "no queue supports {:?} operations", operation),
+			DeviceCreationError::UnsupportedFeatures(features) => write!(fmt, "the device does not support required features: {:?}", features),
+		}
+	}
+}
+impl std::error::Error for DeviceCreationError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			DeviceCreationError::Logical(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl std::fmt::Display for SubmitError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			SubmitError::Commands(err) => write!(fmt, "failed to execute the commands: {}", err),
+			SubmitError::Flush(err) => write!(fmt, "failed to flush the commands: {}", err),
+		}
+	}
+}
+impl std::error::Error for SubmitError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			SubmitError::Commands(err) => Some(err),
+			SubmitError::Flush(err) => Some(err),
+		}
+	}
+}
+
 impl std::fmt::Debug for Device {
 	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
 		writeln!(fmt, "{{")?;
@@ -111,22 +654,49 @@ impl std::fmt::Debug for Device {
 }
 
 
-fn select_physical_device(context: &Context) -> Result<PhysicalDevice, DeviceCreationError> {
+// Get the required features that are not supported, if any.
+fn missing_features(required: &DeviceFeatures, supported: &DeviceFeatures) -> Option<DeviceFeatures> {
+	let missing = DeviceFeatures {
+		geometry_shader: required.geometry_shader && !supported.geometry_shader,
+		tessellation_shader: required.tessellation_shader && !supported.tessellation_shader,
+		wide_lines: required.wide_lines && !supported.wide_lines,
+		sampler_anisotropy: required.sampler_anisotropy && !supported.sampler_anisotropy,
+		fill_mode_non_solid: required.fill_mode_non_solid && !supported.fill_mode_non_solid,
+		depth_clamp: required.depth_clamp && !supported.depth_clamp,
+		logic_op: required.logic_op && !supported.logic_op,
+		sample_rate_shading: required.sample_rate_shading && !supported.sample_rate_shading,
+	};
+	match missing == DeviceFeatures::default() {
+		true => None,
+		false => Some(missing),
+	}
+}
+
+fn select_physical_device<'a>(context: &'a Context, config: &DeviceConfig) -> Result<PhysicalDevice<'a>, DeviceCreationError> {
+	let (headless, require_compute) = (config.headless, requires_compute(config));
 	let mut devices = PhysicalDevice::enumerate(&context.instance);
 	let mut device = match devices.next() {
 		Some(device) => device,
 		None => return Err(DeviceCreationError::NoPhysicalDevicesFound),
 	};
 
-	for other in devices { device = choose_better_device(device, other); };
+	for other in devices { device = choose_better_device(device, other, headless, require_compute); };
 	
-	match validate_physical_device(&device) {
+	match validate_physical_device(&device, headless, require_compute) {
 		true => Ok(device),
+		false if require_compute && validate_physical_device(&device, headless, false) => Err(DeviceCreationError::ComputeNotSupported),
 		false => Err(DeviceCreationError::NoCompatiblePhysicalDeviceFound),
 	}
 }
 
-fn select_queue_families<'a>(device: &PhysicalDevice<'a>) -> Vec<(vulkano::instance::QueueFamily<'a>, f32)> {
+// Queue families to request and which of them are separate queues.
+struct QueueSelection<'a> {
+	families: Vec<(vulkano::instance::QueueFamily<'a>, f32)>,
+	separate_transfer: bool,
+	separate_compute: bool,
+}
+
+fn select_queue_families<'a>(device: &PhysicalDevice<'a>, config: &DeviceConfig) -> QueueSelection<'a> {
 	let mut families = device.queue_families();
 	let first = families.next().unwrap();
 
@@ -140,38 +710,95 @@ fn select_queue_families<'a>(device: &PhysicalDevice<'a>) -> Vec<(vulkano::insta
 		compute = choose_better_compute_family(compute, other);
 	};
 
+	// Headless devices may lack graphics support, in which case the main queue is a compute one.
+	if config.headless && !graphics.supports_graphics() { graphics = compute.clone(); }
+
+	let priorities = config.queue_priorities;
+	let (want_transfer, want_compute) = match config.queue_policy {
+		QueuePolicy::SingleQueue => (false, false),
+		QueuePolicy::Separate { transfer, compute } => (transfer, compute),
+	};
+
 	// Hacky cast abuse, append if the queues_count is larger than number of collisions
-	let append_transfer = transfer.queues_count() > (transfer.id() == graphics.id()) as usize;
-	let append_compute = compute.queues_count() > (compute.id() == graphics.id() || compute.id() == transfer.id()) as usize + append_transfer as usize;
+	let separate_transfer = want_transfer && transfer.queues_count() > (transfer.id() == graphics.id()) as usize;
+	let separate_compute = want_compute && compute.queues_count() > (compute.id() == graphics.id()) as usize + (separate_transfer && compute.id() == transfer.id()) as usize;
 
 	let mut result = Vec::new();
-	result.push((graphics, 1.0));
-	if append_transfer { result.push((transfer, 0.5)); }
-	if append_compute { result.push((compute, 0.25)); }
+	result.push((graphics, priorities.graphics));
+	if separate_transfer { result.push((transfer, priorities.transfer)); }
+	if separate_compute { result.push((compute, priorities.compute)); }
 
-	result
+	QueueSelection { families: result, separate_transfer, separate_compute }
 }
 
-fn unpack_queues(mut queues: Vec<Arc<DeviceQueue>>) -> [Arc<DeviceQueue>; 3] {
-	match queues.len() {
-		1 => {
-			let q = queues.pop().unwrap();
-			[q.clone(), q.clone(), q]
+fn unpack_queues(queues: Vec<Arc<DeviceQueue>>, selection: &QueueSelection) -> [Arc<DeviceQueue>; 3] {
+	assert_eq!(queues.len(), selection.families.len(), "Unexpected number of queues created, something went wrong during device initialization.");
+
+	// The queues are created in the same order they were requested.
+	let mut queues = queues.into_iter();
+	let graphics = queues.next().unwrap();
+	let transfer = match selection.separate_transfer {
+		true => queues.next().unwrap(),
+		false => graphics.clone(),
+	};
+	let compute = match selection.separate_compute {
+		true => queues.next().unwrap(),
+		false => graphics.clone(),
+	};
+	[graphics, transfer, compute]
+}
+
+// Make sure the queues are able to perform the operations they are used for, falling back to the graphics queue if they are not.
+fn validate_queues([graphics, transfer, compute]: [Arc<DeviceQueue>; 3], headless: bool, require_compute: bool) -> Result<[Arc<DeviceQueue>; 3], DeviceCreationError> {
+	if !headless && !supports_operation(&graphics, QueueOperation::Graphics) {
+		return Err(DeviceCreationError::QueueCapabilityMismatch(QueueOperation::Graphics));
+	}
+
+	let transfer = match supports_operation(&transfer, QueueOperation::Transfer) {
+		true => transfer,
+		false => match supports_operation(&graphics, QueueOperation::Transfer) {
+			true => graphics.clone(),
+			false => return Err(DeviceCreationError::QueueCapabilityMismatch(QueueOperation::Transfer)),
 		},
-		// TODO: implement unpacking 2 queues
-		2 => panic!("Unimplemented unpack_queues for just 2 queues, bug Griffone!"),
-		3 => {
-			// TODO: make sure the queues are able to do the thing they were supposed to!
-			let compute = queues.pop().unwrap();
-			let transfer = queues.pop().unwrap();
-			let graphics = queues.pop().unwrap();
-			[graphics, transfer, compute]
+	};
+
+	let compute = match supports_operation(&compute, QueueOperation::Compute) {
+		true => compute,
+		// without required compute support the compute queue is the graphics one regardless
+		false => match supports_operation(&graphics, QueueOperation::Compute) || !require_compute {
+			true => graphics.clone(),
+			false => return Err(DeviceCreationError::QueueCapabilityMismatch(QueueOperation::Compute)),
 		},
-		_ => panic!("Unexpected number of queues created, something wend wrong during device initialization.")
+	};
+
+	Ok([graphics, transfer, compute])
+}
+
+// Decide how resources used by queues of given families should be shared, duplicate families are ignored.
+fn family_sharing(families: &[u32]) -> FamilySharing {
+	let mut distinct = families.to_vec();
+	distinct.sort_unstable();
+	distinct.dedup();
+	match distinct.len() {
+		1 => FamilySharing::Exclusive(distinct[0]),
+		_ => FamilySharing::Concurrent(distinct),
 	}
 }
 
-fn validate_physical_device<'a>(device: &PhysicalDevice<'a>) -> bool {
+fn supports_operation(queue: &DeviceQueue, operation: QueueOperation) -> bool {
+	let family = queue.family();
+	match operation {
+		QueueOperation::Graphics => family.supports_graphics(),
+		// Graphics and compute queues implicitly support transfer operations.
+		QueueOperation::Transfer => family.explicitly_supports_transfers() || family.supports_graphics() || family.supports_compute(),
+		QueueOperation::Compute => family.supports_compute(),
+	}
+}
+
+// Headless devices are only useful for computations, so they always require compute support.
+fn requires_compute(config: &DeviceConfig) -> bool { config.headless || config.require_compute }
+
+fn validate_physical_device<'a>(device: &PhysicalDevice<'a>, headless: bool, require_compute: bool) -> bool {
 	if device.api_version() < super::REQUIRED_VULKAN_VERSION { return false; }
 
 	let mut supports_graphics = false;
@@ -182,11 +809,11 @@ fn validate_physical_device<'a>(device: &PhysicalDevice<'a>) -> bool {
 		supports_compute = supports_compute || (family.queues_count() > 0 && family.supports_compute());
 	};
 
-	supports_compute && supports_graphics
+	(supports_compute || !require_compute) && (headless || supports_graphics)
 }
 
-fn choose_better_device<'a>(first: PhysicalDevice<'a>, second: PhysicalDevice<'a>) -> PhysicalDevice<'a> {
-	if !validate_physical_device(&second) { return first; };
+fn choose_better_device<'a>(first: PhysicalDevice<'a>, second: PhysicalDevice<'a>, headless: bool, require_compute: bool) -> PhysicalDevice<'a> {
+	if !validate_physical_device(&second, headless, require_compute) { return first; };
 
 	// TODO: compare and select best device
 	first
@@ -235,3 +862,29 @@ fn write_queue_details(fmt: &mut std::fmt::Formatter, queue: &DeviceQueue, prefi
 	writeln!(fmt, "{}compute: {}", prefix, family.supports_compute())?;
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_missing_features() {
+		let supported = DeviceFeatures { sampler_anisotropy: true, depth_clamp: true, .. DeviceFeatures::default() };
+
+		assert_eq!(missing_features(&DeviceFeatures::default(), &supported), None);
+		assert_eq!(missing_features(&DeviceFeatures { sampler_anisotropy: true, .. DeviceFeatures::default() }, &supported), None);
+
+		let required = DeviceFeatures { sampler_anisotropy: true, wide_lines: true, .. DeviceFeatures::default() };
+		assert_eq!(missing_features(&required, &supported), Some(DeviceFeatures { wide_lines: true, .. DeviceFeatures::default() }));
+	}
+
+	#[test]
+	fn test_family_sharing() {
+		// single family used for all operations
+		assert_eq!(family_sharing(&[0, 0, 0]), FamilySharing::Exclusive(0));
+		// dedicated transfer and compute families
+		assert_eq!(family_sharing(&[0, 2, 1]), FamilySharing::Concurrent(vec![0, 1, 2]));
+		// compute shares the graphics family
+		assert_eq!(family_sharing(&[1, 0, 1]), FamilySharing::Concurrent(vec![0, 1]));
+	}
+}
@@ -0,0 +1,206 @@
+//! Format provides helpers for querying properties of image formats.
+//!
+//! Prefer these over inspecting [`Format`](enum.Format.html) directly, so the logic stays consistent across **gaclen**.
+//!
+//! # Data formats.
+//!
+//! Passes that write non-color data (object IDs for picking, motion vectors, etc.) can use single- or dual-channel formats.
+//! The following are required by Vulkan to be usable as color attachments on every device:
+//! - `R8Unorm`, `R8G8Unorm`, `R8G8B8A8Unorm`
+//! - `R16Sfloat`, `R16G16Sfloat`, `R16G16B16A16Sfloat`
+//! - `R16Uint`, `R16Sint`, `R32Uint`, `R32Sint`, `R32G32Uint` and other integer formats with 1, 2 or 4 components
+//! - `R32Sfloat`, `R32G32Sfloat`, `R32G32B32A32Sfloat`
+//!
+//! Three-component formats (such as `R32G32B32Sfloat`) are rarely renderable, prefer their four-component counterparts.
+//! Use [`supports_color_attachment()`](fn.supports_color_attachment.html) to check other formats.
+
+use super::device::Device;
+
+use vulkano::format::FormatTy;
+
+pub use vulkano::format::Format;
+
+// Depth formats in order of preference, D16Unorm is guaranteed to be supported by all devices.
+const DEPTH_FORMAT_PREFERENCE: [Format; 5] = [
+	Format::D32Sfloat,
+	Format::D32Sfloat_S8Uint,
+	Format::D24Unorm_S8Uint,
+	Format::X8_D24UnormPack32,
+	Format::D16Unorm,
+];
+
+/// Check whether the format has a depth component.
+pub fn has_depth(format: Format) -> bool {
+	match format.ty() {
+		FormatTy::Depth | FormatTy::DepthStencil => true,
+		_ => false,
+	}
+}
+
+/// Check whether the format has a stencil component.
+pub fn has_stencil(format: Format) -> bool {
+	match format.ty() {
+		FormatTy::Stencil | FormatTy::DepthStencil => true,
+		_ => false,
+	}
+}
+
+/// Check whether the format is *sRGB*-encoded.
+///
+/// Only uncompressed formats are currently recognized.
+pub fn is_srgb(format: Format) -> bool {
+	match format {
+		Format::R8Srgb
+		| Format::R8G8Srgb
+		| Format::R8G8B8Srgb
+		| Format::B8G8R8Srgb
+		| Format::R8G8B8A8Srgb
+		| Format::B8G8R8A8Srgb
+		| Format::A8B8G8R8SrgbPack32 => true,
+		_ => false,
+	}
+}
+
+/// Convert the depth aspect of a depth format, as copied into a buffer, into depth values.
+///
+/// Handles the packing of each format, notably the 24-bit depth of `D24Unorm_S8Uint` and `X8_D24UnormPack32` stored in 4 bytes.
+/// Returns `None` if the format has no depth component.
+pub fn unpack_depth(format: Format, data: &[u8]) -> Option<Vec<f32>> {
+	let read_u16 = |bytes: &[u8]| u16::from_ne_bytes([bytes[0], bytes[1]]);
+	let read_u32 = |bytes: &[u8]| u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+	match format {
+		Format::D16Unorm | Format::D16Unorm_S8Uint => Some(data.chunks_exact(2).map(|texel| read_u16(texel) as f32 / 65_535.0).collect()),
+		Format::D24Unorm_S8Uint | Format::X8_D24UnormPack32 => Some(data.chunks_exact(4).map(|texel| (read_u32(texel) & 0x00FF_FFFF) as f32 / 16_777_215.0).collect()),
+		Format::D32Sfloat | Format::D32Sfloat_S8Uint => Some(data.chunks_exact(4).map(|texel| f32::from_bits(read_u32(texel))).collect()),
+		_ => None,
+	}
+}
+
+/// Get the size of a single pixel of the format in bytes.
+///
+/// Returns `None` for compressed formats, which don't have a per-pixel size, see [`block_size()`](fn.block_size.html).
+pub fn bytes_per_pixel(format: Format) -> Option<usize> {
+	// vulkano reports the size of a whole block for block-compressed formats
+	match block_size(format) {
+		Some(_) => None,
+		None => format.size(),
+	}
+}
+
+/// Get the dimensions (in pixels) and size (in bytes) of a single block of a block-compressed format.
+///
+/// Returns `None` for uncompressed formats.
+/// Only *BC*, *ETC2*, *EAC* and *ASTC* formats are currently recognized.
+pub fn block_size(format: Format) -> Option<([u32; 2], usize)> {
+	match format {
+		Format::BC1_RGBUnormBlock
+		| Format::BC1_RGBSrgbBlock
+		| Format::BC1_RGBAUnormBlock
+		| Format::BC1_RGBASrgbBlock
+		| Format::BC4UnormBlock
+		| Format::BC4SnormBlock
+		| Format::ETC2_R8G8B8UnormBlock
+		| Format::ETC2_R8G8B8SrgbBlock
+		| Format::ETC2_R8G8B8A1UnormBlock
+		| Format::ETC2_R8G8B8A1SrgbBlock
+		| Format::EAC_R11UnormBlock
+		| Format::EAC_R11SnormBlock => Some(([4, 4], 8)),
+		Format::BC2UnormBlock
+		| Format::BC2SrgbBlock
+		| Format::BC3UnormBlock
+		| Format::BC3SrgbBlock
+		| Format::BC5UnormBlock
+		| Format::BC5SnormBlock
+		| Format::BC6HUfloatBlock
+		| Format::BC6HSfloatBlock
+		| Format::BC7UnormBlock
+		| Format::BC7SrgbBlock
+		| Format::ETC2_R8G8B8A8UnormBlock
+		| Format::ETC2_R8G8B8A8SrgbBlock
+		| Format::EAC_R11G11UnormBlock
+		| Format::EAC_R11G11SnormBlock
+		| Format::ASTC_4x4UnormBlock
+		| Format::ASTC_4x4SrgbBlock => Some(([4, 4], 16)),
+		Format::ASTC_5x4UnormBlock | Format::ASTC_5x4SrgbBlock => Some(([5, 4], 16)),
+		Format::ASTC_5x5UnormBlock | Format::ASTC_5x5SrgbBlock => Some(([5, 5], 16)),
+		Format::ASTC_6x5UnormBlock | Format::ASTC_6x5SrgbBlock => Some(([6, 5], 16)),
+		Format::ASTC_6x6UnormBlock | Format::ASTC_6x6SrgbBlock => Some(([6, 6], 16)),
+		Format::ASTC_8x5UnormBlock | Format::ASTC_8x5SrgbBlock => Some(([8, 5], 16)),
+		Format::ASTC_8x6UnormBlock | Format::ASTC_8x6SrgbBlock => Some(([8, 6], 16)),
+		Format::ASTC_8x8UnormBlock | Format::ASTC_8x8SrgbBlock => Some(([8, 8], 16)),
+		Format::ASTC_10x5UnormBlock | Format::ASTC_10x5SrgbBlock => Some(([10, 5], 16)),
+		Format::ASTC_10x6UnormBlock | Format::ASTC_10x6SrgbBlock => Some(([10, 6], 16)),
+		Format::ASTC_10x8UnormBlock | Format::ASTC_10x8SrgbBlock => Some(([10, 8], 16)),
+		Format::ASTC_10x10UnormBlock | Format::ASTC_10x10SrgbBlock => Some(([10, 10], 16)),
+		Format::ASTC_12x10UnormBlock | Format::ASTC_12x10SrgbBlock => Some(([12, 10], 16)),
+		Format::ASTC_12x12UnormBlock | Format::ASTC_12x12SrgbBlock => Some(([12, 12], 16)),
+		_ => None,
+	}
+}
+
+/// Check whether the format can be sampled from by the device.
+pub fn supports_sampling(device: &Device, format: Format) -> bool {
+	format.properties(device.physical_device()).optimal_tiling_features.sampled_image
+}
+
+/// Check whether the format can be used as a color attachment by the device.
+pub fn supports_color_attachment(device: &Device, format: Format) -> bool {
+	format.properties(device.physical_device()).optimal_tiling_features.color_attachment
+}
+
+/// Get the best depth format that can be used as a depth attachment by the device.
+///
+/// Floating-point formats are preferred for their precision, especially with inverse depth.
+pub fn recommended_depth_format(device: &Device) -> Format {
+	let physical = device.physical_device();
+	DEPTH_FORMAT_PREFERENCE.iter()
+		.cloned()
+		.find(|format| format.properties(physical).optimal_tiling_features.depth_stencil_attachment)
+		.unwrap_or(Format::D16Unorm)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_aspects() {
+		assert!(has_depth(Format::D16Unorm));
+		assert!(!has_stencil(Format::D16Unorm));
+		assert!(has_depth(Format::D24Unorm_S8Uint));
+		assert!(has_stencil(Format::D24Unorm_S8Uint));
+		assert!(!has_depth(Format::R8G8B8A8Unorm));
+		assert!(!has_stencil(Format::R8G8B8A8Unorm));
+	}
+
+	#[test]
+	fn test_is_srgb() {
+		assert!(is_srgb(Format::B8G8R8A8Srgb));
+		assert!(!is_srgb(Format::B8G8R8A8Unorm));
+	}
+
+	#[test]
+	fn test_block_size() {
+		assert_eq!(block_size(Format::R8G8B8A8Unorm), None);
+		assert_eq!(block_size(Format::BC1_RGBAUnormBlock), Some(([4, 4], 8)));
+		assert_eq!(block_size(Format::BC7SrgbBlock), Some(([4, 4], 16)));
+		assert_eq!(block_size(Format::ASTC_8x6UnormBlock), Some(([8, 6], 16)));
+	}
+
+	#[test]
+	fn test_unpack_depth() {
+		assert_eq!(unpack_depth(Format::R8G8B8A8Unorm, &[0; 4]), None);
+		assert_eq!(unpack_depth(Format::D32Sfloat, &0.25f32.to_ne_bytes()), Some(vec![0.25]));
+		assert_eq!(unpack_depth(Format::D16Unorm, &u16::MAX.to_ne_bytes()), Some(vec![1.0]));
+		// The upper 8 bits are undefined and should be ignored.
+		assert_eq!(unpack_depth(Format::D24Unorm_S8Uint, &0xFFFF_FFFFu32.to_ne_bytes()), Some(vec![1.0]));
+		assert_eq!(unpack_depth(Format::X8_D24UnormPack32, &0xAB00_0000u32.to_ne_bytes()), Some(vec![0.0]));
+	}
+
+	#[test]
+	fn test_bytes_per_pixel() {
+		assert_eq!(bytes_per_pixel(Format::R8G8B8A8Unorm), Some(4));
+		assert_eq!(bytes_per_pixel(Format::D32Sfloat), Some(4));
+		assert_eq!(bytes_per_pixel(Format::BC1_RGBUnormBlock), None);
+	}
+}
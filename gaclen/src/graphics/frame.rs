@@ -11,33 +11,45 @@
 //! 3. Invoke one or more [`draw`](struct.PassInFrame.html#method.draw) calls in order to draw the geometry.
 //! 4. End the pass (by calling [`PassInFrame::finish_pass()`](struct.PassInFrame.html#method.finish_pass)).
 //! 5. (optionally) repeat steps 2-4.
+//! 
+//! Each pass uses only the viewports it was begun with, so passes drawing to framebuffers of different sizes (such as a shadow map followed by the window image) can be mixed in a single frame.
+//! [`viewport_for_framebuffer()`](fn.viewport_for_framebuffer.html) creates a viewport covering a whole framebuffer.
 //! 6. Finish the frame by calling [`Frame::finish()`](struct.Frame.html#method.finish).
 //! 
+//! For drawing a lot of objects the draw calls can be recorded on multiple threads:
+//! 1. Record [`SecondaryCommands`](struct.SecondaryCommands.html) using [`GraphicalPass::start_secondary_commands()`](../pass/struct.GraphicalPass.html#method.start_secondary_commands) on any thread.
+//! 2. Begin the pass using [`Frame::begin_pass_with_secondaries()`](struct.Frame.html#method.begin_pass_with_secondaries).
+//! 3. Invoke [`PassInFrame::execute_secondary()`](struct.PassInFrame.html#method.execute_secondary) with the recorded commands.
+//! 
 //! Note that **gaclen** hides some of the underlying mechanisms for ease of use, namely - [command buffers](https://vulkan.lunarg.com/doc/view/1.0.26.0/linux/vkspec.chunked/ch05.html), a core concept in using vulkan effectively.
 //! The `GPU` is technically does not receive any commands until `Frame::finish()` is invoked.
 //! The *draw* calls correspond to recording GPU commands related to drawing given data with given context, but the execution happens completely separately after [`Frame::finish()`](struct.Frame.html#method.finish) is invoked.
 //! Additionally the frame will be presented (shown on the screen) as soon as it's available, depending exactly on the [`Swapchain`](struct.Swapchain.html) being used.
 //! Currently there is no functionality to wait until a frame is drawn or draw a frame without presenting it.
 
-use super::device::Device;
+use super::device::{CommandContext, Device, FrameFence};
 use super::pass::GraphicalPass;
 use super::swapchain::Swapchain;
 
 use winit::window::Window;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 pub use vulkano::pipeline::viewport::Viewport;
+pub use vulkano::swapchain::AcquireError;
+pub use vulkano::swapchain::RectangleLayer as PresentRectangle;
+pub use vulkano::sync::FlushError;
 
 use vulkano::buffer::{BufferAccess, TypedBufferAccess};
-use vulkano::command_buffer::{AutoCommandBufferBuilder, CommandBufferExecError, DynamicState};
+use vulkano::command_buffer::{AutoCommandBuffer, AutoCommandBufferBuilder, CommandBufferExecError, DynamicState};
 use vulkano::descriptor::descriptor_set::DescriptorSetsCollection;
-use vulkano::framebuffer::FramebufferAbstract;
-use vulkano::sync::{GpuFuture, FlushError};
-use vulkano::swapchain::{Swapchain as VlkSwapchain};
+use vulkano::framebuffer::{FramebufferAbstract, Subpass};
+use vulkano::sync::GpuFuture;
+use vulkano::swapchain::{PresentRegion, Swapchain as VlkSwapchain};
 use vulkano::pipeline::GraphicsPipelineAbstract;
 use vulkano::pipeline::input_assembly::Index;
-use vulkano::pipeline::vertex::VertexSource;
+use vulkano::pipeline::vertex::{BufferlessVertices, VertexSource};
 
 /// A frame in the process of being drawn.
 pub struct Frame {
@@ -48,6 +60,8 @@ pub struct Frame {
 	pub(super) commands: AutoCommandBufferBuilder,
 	// index of the frame in the swapchain
 	pub(super) swapchain_index: usize,
+	// the acquired image no longer matches the surface exactly
+	pub(super) suboptimal: bool,
 }
 
 /// A frame in the process of being drawn using a given [`GraphicalPass`](../pass/struct.GraphicalPass.html).
@@ -56,6 +70,41 @@ pub struct PassInFrame<'a, P : ?Sized> {
 	pub(super) pass: &'a GraphicalPass<P>,
 }
 
+/// Commands recorded separately from a [`Frame`](struct.Frame.html), possibly on a different thread.
+/// 
+/// Should be executed within a [`PassInFrame`](struct.PassInFrame.html) using the same pass they were recorded for.
+pub struct SecondaryCommands {
+	pub(super) commands: AutoCommandBuffer,
+}
+
+/// A structure for recording [`SecondaryCommands`](struct.SecondaryCommands.html) using a given [`GraphicalPass`](../pass/struct.GraphicalPass.html).
+pub struct SecondaryCommandsBuilder<'a, P : ?Sized> {
+	pass: &'a GraphicalPass<P>,
+	commands: AutoCommandBufferBuilder,
+	dynamic_state: DynamicState,
+}
+
+/// Error beginning the frame with a timeout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FrameBeginError {
+	/// No swapchain image became available before the timeout.
+	/// 
+	/// This is recoverable, the frame should be skipped and attempted again later.
+	Timeout,
+	/// Error during acquiring the swapchain image.
+	Acquire(AcquireError),
+}
+
+/// Result of [`Frame::try_begin()`](struct.Frame.html#method.try_begin).
+pub enum BeginResult {
+	/// The frame is ready to be drawn.
+	Ready(Frame),
+	/// The swapchain no longer matches the surface (typically after a resize) and should be [resized](../swapchain/struct.Swapchain.html#method.resize) before beginning a frame again.
+	RecreateSwapchain(Device),
+	/// Error during acquiring the swapchain image.
+	Failed(Device, AcquireError),
+}
+
 /// Error finishing the frame.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum FrameFinishError {
@@ -65,6 +114,21 @@ pub enum FrameFinishError {
 	Commands(CommandBufferExecError),
 }
 
+/// Error during recording a draw command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrawError {
+	/// The index buffer was not created with the `index_buffer` usage.
+	IndexBufferMissingUsage,
+}
+
+/// Create a viewport covering the whole of given framebuffer with the default `0.0..1.0` depth range.
+/// 
+/// Useful for passes that draw to framebuffers of a different size than the window, such as shadow maps.
+pub fn viewport_for_framebuffer(framebuffer: &impl FramebufferAbstract) -> Viewport {
+	let [width, height, _] = framebuffer.dimensions();
+	Viewport { origin: [0.0; 2], dimensions: [width as f32, height as f32], depth_range: 0.0..1.0 }
+}
+
 impl Frame {
 	/// Begin drawing a frame.
 	/// 
@@ -72,16 +136,67 @@ impl Frame {
 	/// - Acquires the swapchain image to draw to.
 	/// - Creates a CommandBuffer to be recorded for the frame.
 	/// 
+	/// Blocks until a swapchain image becomes available, which may never happen with a hung presentation engine.
+	/// Use [`Frame::begin_with_timeout()`](struct.Frame.html#method.begin_with_timeout) to limit the wait instead.
+	/// 
 	/// NOTE: that to actually draw, [`Frame::begin_pass()`](struct.Frame.html#method.begin_pass) needs to be called.
 	pub fn begin(
+		device: Device,
+		swapchain: &Swapchain,
+	) -> Result<Frame, (Device, AcquireError)>
+	{
+		Frame::acquire(device, swapchain, None)
+	}
+
+	/// Begin drawing a frame, reporting an out of date swapchain instead of failing.
+	/// 
+	/// Same as [`Frame::begin()`](struct.Frame.html#method.begin), but `AcquireError::OutOfDate` results in [`BeginResult::RecreateSwapchain`](enum.BeginResult.html#variant.RecreateSwapchain), which is the expected outcome of resizing a window:
+	/// ```ignore
+	/// let frame = match Frame::try_begin(device, &swapchain) {
+	/// 	BeginResult::Ready(frame) => frame,
+	/// 	BeginResult::RecreateSwapchain(device) => { swapchain.resize(window.inner_size().into())?; return },
+	/// 	BeginResult::Failed(device, err) => panic!("{}", err),
+	/// };
+	/// ```
+	/// A frame that can still be drawn while no longer matching the surface exactly is [ready](enum.BeginResult.html#variant.Ready), check [`Frame::should_recreate_swapchain()`](struct.Frame.html#method.should_recreate_swapchain) for it.
+	pub fn try_begin(
+		device: Device,
+		swapchain: &Swapchain,
+	) -> BeginResult
+	{
+		match Frame::acquire(device, swapchain, None) {
+			Ok(frame) => BeginResult::Ready(frame),
+			Err((device, AcquireError::OutOfDate)) => BeginResult::RecreateSwapchain(device),
+			Err((device, err)) => BeginResult::Failed(device, err),
+		}
+	}
+
+	/// Begin drawing a frame, waiting at most `timeout` for a swapchain image to become available.
+	/// 
+	/// Same as [`Frame::begin()`](struct.Frame.html#method.begin), but does not block indefinitely on a hung presentation engine.
+	/// A timeout results in [`FrameBeginError::Timeout`](enum.FrameBeginError.html#variant.Timeout), in which case the frame should be skipped.
+	pub fn begin_with_timeout(
+		device: Device,
+		swapchain: &Swapchain,
+		timeout: Duration,
+	) -> Result<Frame, (Device, FrameBeginError)>
+	{
+		match Frame::acquire(device, swapchain, Some(timeout)) {
+			Ok(frame) => Ok(frame),
+			Err((device, AcquireError::Timeout)) => Err((device, FrameBeginError::Timeout)),
+			Err((device, err)) => Err((device, FrameBeginError::Acquire(err))),
+		}
+	}
+
+	fn acquire(
 		mut device: Device,
 		swapchain: &Swapchain,
-	) -> Result<Frame, (Device, vulkano::swapchain::AcquireError)>
+		timeout: Option<Duration>,
+	) -> Result<Frame, (Device, AcquireError)>
 	{
 		let used_swapchain = swapchain.swapchain.clone();
 
-		// TODO: propagate the should_recreate flag outside.
-		let (swapchain_index, _should_recreate, image_acquire_time) = match vulkano::swapchain::acquire_next_image(used_swapchain.clone(), None) {
+		let (swapchain_index, suboptimal, image_acquire_time) = match vulkano::swapchain::acquire_next_image(used_swapchain.clone(), timeout) {
 			Ok(result) => result,
 			Err(err) => return Err((device, err)),
 		};
@@ -103,19 +218,41 @@ impl Frame {
 			time,
 			commands,
 			swapchain_index,
+			suboptimal,
 		};
 		Ok(frame)
 	}
 
+	/// Check whether the acquired swapchain image is suboptimal for the surface, for example after the window was resized.
+	/// 
+	/// The frame can still be drawn and presented, but the result may be stretched.
+	/// Recreating the swapchain (using [`Swapchain::resize()`](../swapchain/struct.Swapchain.html#method.resize)) before drawing the next frame fixes that.
+	/// Independent of [`FlushError::OutOfDate`](enum.FlushError.html#variant.OutOfDate) returned during presentation, which requires recreating the swapchain.
+	/// 
+	/// Only reports the status of acquiring the image, `vulkano` 0.18 drops the suboptimal status returned by presentation, so [`Frame::finish()`](#method.finish) can not report it.
+	#[inline]
+	pub fn should_recreate_swapchain(&self) -> bool { self.suboptimal }
+
+	/// Get the index of the swapchain image the frame is drawn to, in `[0, image_count)` range.
+	/// 
+	/// Useful for selecting per-frame resources, see [`Swapchain::image_count()`](../swapchain/struct.Swapchain.html#method.image_count).
+	#[inline]
+	pub fn swapchain_index(&self) -> usize { self.swapchain_index }
+
+	// TODO/vulkano: GPU timestamp queries for profiling passes (behind a `gpu-profiling` feature), vulkano 0.18 command buffer builders can not write timestamps.
+
 	/// Begins using a [`GraphicalPass`](../pass/struct.GraphicalPass.html).
 	/// 
 	/// Switches the GPU state to use a provided pass' pipeline for drawing.
 	/// 
+	/// The clear values should match the attachments of the pass, see [`GraphicalPass::validate_clear_values()`](../pass/struct.GraphicalPass.html#method.validate_clear_values).
+	/// 
 	/// # Panic.
 	/// 
-	/// - Panics if fails to begin the [renderpass](https://vulkan.lunarg.com/doc/view/1.0.37.0/linux/vkspec.chunked/ch07.html) command.
+	/// - Panics if fails to begin the [renderpass](https://vulkan.lunarg.com/doc/view/1.0.37.0/linux/vkspec.chunked/ch07.html) command, which includes mismatched clear values.
+	/// - Panics if the pass expects more than 1 viewport.
 	pub fn begin_pass<'a, P: ?Sized, F>(
-		mut self,
+		self,
 		pass: &'a GraphicalPass<P>,
 		framebuffer: F,
 		viewport: Viewport,
@@ -124,10 +261,58 @@ impl Frame {
 	where
 		F : FramebufferAbstract + Send + Sync + Clone + 'static,
 	{
+		self.begin_pass_with_viewports(pass, framebuffer, vec![viewport], clear_values)
+	}
+
+	/// Begins using a [`GraphicalPass`](../pass/struct.GraphicalPass.html) that draws to multiple viewports.
+	/// 
+	/// Same as [`Frame::begin_pass()`](struct.Frame.html#method.begin_pass), but with as many viewports as the pass was [built with](../pass/struct.GraphicalPassBuilder.html#method.viewport_count).
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to begin the [renderpass](https://vulkan.lunarg.com/doc/view/1.0.37.0/linux/vkspec.chunked/ch07.html) command.
+	/// - Panics if the number of viewports does not match the one of the pass.
+	pub fn begin_pass_with_viewports<'a, P: ?Sized, F>(
+		mut self,
+		pass: &'a GraphicalPass<P>,
+		framebuffer: F,
+		viewports: Vec<Viewport>,
+		clear_values: Vec<vulkano::format::ClearValue>)
+	-> PassInFrame<'a, P>
+	where
+		F : FramebufferAbstract + Send + Sync + Clone + 'static,
+	{
+		assert_eq!(viewports.len(), pass.state.viewport_count as usize, "The number of viewports does not match the one of the pass.");
+
 		// TODO: build framebuffer automatically, using GraphicalRenderPassDescriptor information
 
 		self.commands = self.commands.begin_render_pass(framebuffer, false, clear_values).unwrap();
-		self.dynamic_state.viewports = Some(vec![viewport]);
+		// reset all dynamic state, so nothing leaks from the previous pass
+		self.dynamic_state = pass_dynamic_state(viewports);
+
+		PassInFrame {
+			frame: self,
+			pass: pass,
+		}
+	}
+
+	/// Begins using a [`GraphicalPass`](../pass/struct.GraphicalPass.html) with commands recorded separately.
+	/// 
+	/// Same as [`Frame::begin_pass()`](struct.Frame.html#method.begin_pass), but the resulting pass may only [execute](struct.PassInFrame.html#method.execute_secondary) [`SecondaryCommands`](struct.SecondaryCommands.html), drawing directly will fail.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to begin the [renderpass](https://vulkan.lunarg.com/doc/view/1.0.37.0/linux/vkspec.chunked/ch07.html) command.
+	pub fn begin_pass_with_secondaries<'a, P: ?Sized, F>(
+		mut self,
+		pass: &'a GraphicalPass<P>,
+		framebuffer: F,
+		clear_values: Vec<vulkano::format::ClearValue>)
+	-> PassInFrame<'a, P>
+	where
+		F : FramebufferAbstract + Send + Sync + Clone + 'static,
+	{
+		self.commands = self.commands.begin_render_pass(framebuffer, true, clear_values).unwrap();
 
 		PassInFrame {
 			frame: self,
@@ -139,26 +324,48 @@ impl Frame {
 	/// 
 	/// Releases the Device to allow starting a new frame, allocate new resources and anything else a [`Device`](struct.Device.html) is able to do.
 	/// 
+	/// The Device is released in a reusable state even if the frame fails to finish (for example with [`FlushError::OutOfDate`](enum.FlushError.html#variant.OutOfDate) after a resize).
+	/// In that case the synchronization state of the Device is reset, so the next frame does not wait for the failed one.
+	/// 
+	/// A successful finish does not mean the swapchain is optimal: `vulkano` 0.18 drops the suboptimal status returned by presentation, so it is not reported here.
+	/// Check [`Frame::should_recreate_swapchain()`](#method.should_recreate_swapchain) before finishing for the status reported when the image was acquired.
+	/// 
 	/// # Panic.
 	/// 
 	/// - Panics if fails to build (finalize) the command buffer.
 	#[inline]
-	pub fn finish(self) -> Result<Device, (Device, FrameFinishError)> {
+	pub fn finish(self) -> Result<Device, (Device, FrameFinishError)> { self.finish_and_present(None) }
+
+	/// Finish drawing the frame and present only the changed regions of it.
+	/// 
+	/// Same as [`Frame::finish()`](struct.Frame.html#method.finish), but hints the presentation engine that only given rectangles of the image changed since the previous frame, which saves bandwidth for mostly static images (such as tool interfaces).
+	/// The rest of the image must still contain valid contents, since the hint may be ignored.
+	/// Falls back to presenting the whole image if the device does not [support incremental presentation](../device/struct.Device.html#method.supports_incremental_present).
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to build (finalize) the command buffer.
+	pub fn finish_with_regions(self, regions: Vec<PresentRectangle>) -> Result<Device, (Device, FrameFinishError)> {
+		self.finish_and_present(Some(PresentRegion { layers: regions }))
+	}
+
+	// TODO/vulkano: report the suboptimal status returned by presentation, vulkano 0.18 `PresentFuture` drops it.
+	fn finish_and_present(self, region: Option<PresentRegion>) -> Result<Device, (Device, FrameFinishError)> {
 		let commands = self.commands.build().unwrap();
-		let after_execute = match self.time.then_execute(self.device.graphics_queue.clone(), commands) {
+		let device = self.device;
+		let after_execute = match self.time.then_execute(device.graphics_queue.clone(), commands) {
 			Ok(future) => future,
-			Err(err) => return Err((self.device, FrameFinishError::Commands(err))),
+			Err(err) => return finish_submission(device, Err(FrameFinishError::Commands(err))),
 		};
 
-		let after_flush = after_execute.then_swapchain_present(self.device.graphics_queue.clone(), self.swapchain, self.swapchain_index)
-			.then_signal_fence_and_flush();
-		
-		let after_frame = match after_flush {
-			Ok(future) => future,
-			Err(err) => return Err((self.device, FrameFinishError::Flush(err))),
+		let queue = device.graphics_queue.clone();
+		let after_present: Box<dyn GpuFuture> = match region {
+			Some(region) if device.supports_incremental_present() =>
+				Box::new(after_execute.then_swapchain_present_incremental(queue, self.swapchain, self.swapchain_index, region)),
+			_ => Box::new(after_execute.then_swapchain_present(queue, self.swapchain, self.swapchain_index)),
 		};
-		let device = Device { before_frame: Some(Box::new(after_frame)), .. self.device };
-		Ok(device)
+		let after_flush = after_present.then_signal_fence_and_flush().map_err(FrameFinishError::Flush);
+		finish_submission(device, after_flush)
 	}
 }
 
@@ -168,6 +375,8 @@ where
 	P : GraphicsPipelineAbstract + Send + Sync + 'static,
 {
 	// TODO: non-polymorphic vertex_buffer drawing
+	// TODO/vulkano: setting depth bias per draw (`set_depth_bias(constant, clamp, slope)`, only for passes built with dynamic depth bias), vulkano 0.18 can not record vkCmdSetDepthBias.
+	// TODO/vulkano: clearing specific attachments mid-pass (vkCmdClearAttachments), not exposed by vulkano 0.18 command buffer builders.
 
 	/// Draw some data using a pass.
 	/// 
@@ -192,6 +401,50 @@ where
 		self
 	}
 
+	/// Draw `vertex_count` vertices without any vertex buffers, such as a fullscreen triangle for post-processing.
+	/// 
+	/// The pass should be built with [`GraphicalPassBuilder::bufferless_input()`](../pass/struct.GraphicalPassBuilder.html#method.bufferless_input), the vertex shader should generate vertices from `gl_VertexIndex`.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to write draw commands to the command buffer.
+	#[inline]
+	pub fn draw_unbound<DSC, PC>(
+		self,
+		vertex_count: usize,
+		descriptor_sets: DSC,
+		push_constants: PC
+	) -> Self
+	where
+		P : VertexSource<BufferlessVertices>,
+		DSC : DescriptorSetsCollection,
+	{
+		self.draw(BufferlessVertices { vertices: vertex_count, instances: 1 }, descriptor_sets, push_constants)
+	}
+
+	/// Draw multiple instances of some vertex data using a pass.
+	/// 
+	/// The pass should use per-vertex and per-instance buffers as input, for example by using [`GraphicalPassBuilder::two_buffers_input()`](../pass/struct.GraphicalPassBuilder.html#method.two_buffers_input).
+	/// The number of drawn instances is the number of elements in the `instance_buffer`.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to write draw commands to the command buffer.
+	#[inline]
+	pub fn draw_instanced<VB, IB, DSC, PC>(
+		self,
+		vertex_buffer: VB,
+		instance_buffer: IB,
+		descriptor_sets: DSC,
+		push_constants: PC
+	) -> Self
+	where
+		P : VertexSource<(VB, IB)>,
+		DSC : DescriptorSetsCollection,
+	{
+		self.draw((vertex_buffer, instance_buffer), descriptor_sets, push_constants)
+	}
+
 	/// Draw some indexed vertex data using a pass.
 	/// 
 	/// The result depends highly on the [`GraphicalPass`](../pass/struct.GraphicalPass.html) that was used to create the [`PassInFrame`](struct.PassInFrame.html).
@@ -200,6 +453,8 @@ where
 	/// # Panic.
 	/// 
 	/// - Panics if fails to write draw commands to the command buffer.
+	/// 
+	/// Use [`try_draw_indexed()`](#method.try_draw_indexed) to validate the index buffer instead.
 	#[inline]
 	pub fn draw_indexed<VB, IB, DSC, PC, I>(
 		mut self,
@@ -218,6 +473,48 @@ where
 		self
 	}
 
+	/// Draw some indexed vertex data using a pass, validating the index buffer first.
+	/// 
+	/// Same as [`draw_indexed()`](#method.draw_indexed), but returns the pass back with an error if the index buffer can not be used for drawing.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to write draw commands to the command buffer for reasons other than an invalid index buffer.
+	#[inline]
+	pub fn try_draw_indexed<VB, IB, DSC, PC, I>(
+		self,
+		vertex_buffer: VB,
+		index_buffer: IB,
+		descriptor_sets: DSC,
+		push_constants: PC
+	) -> Result<Self, (Self, DrawError)>
+	where
+		P : VertexSource<VB>,
+		DSC : DescriptorSetsCollection,
+		IB : BufferAccess + TypedBufferAccess<Content = [I]> + Send + Sync + 'static,
+		I : Index + 'static,
+	{
+		if !index_buffer.inner().buffer.usage_index_buffer() { return Err((self, DrawError::IndexBufferMissingUsage)); }
+
+		Ok(self.draw_indexed(vertex_buffer, index_buffer, descriptor_sets, push_constants))
+	}
+
+	/// Execute commands recorded separately.
+	/// 
+	/// The pass should have been begun using [`Frame::begin_pass_with_secondaries()`](struct.Frame.html#method.begin_pass_with_secondaries).
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to write execute commands to the command buffer.
+	#[inline]
+	pub fn execute_secondary(mut self, secondaries: Vec<SecondaryCommands>) -> Self {
+		for secondary in secondaries {
+			// Safe, since the secondary commands were recorded for a subpass of a GraphicalPass.
+			self.frame.commands = unsafe { self.frame.commands.execute_commands(secondary.commands) }.unwrap();
+		};
+		self
+	}
+
 	/// Finish using a GraphicalPass.
 	/// 
 	/// Releases the consumed [`Frame`](struct.Frame.html) to begin the next pass or finish the frame.
@@ -231,3 +528,177 @@ where
 		Frame { commands, .. self.frame }
 	}
 }
+
+impl<'a, P : ?Sized> SecondaryCommandsBuilder<'a, P>
+where
+	P : GraphicsPipelineAbstract + Send + Sync + 'static,
+{
+	pub(super) fn new(pass: &'a GraphicalPass<P>, context: &CommandContext, viewport: Viewport) -> Self {
+		let subpass = Subpass::from(pass.pipeline.clone(), 0).unwrap();
+		let commands = AutoCommandBufferBuilder::secondary_graphics_one_time_submit(context.device.clone(), context.graphics_queue.family(), subpass).unwrap();
+		let dynamic_state = pass_dynamic_state(vec![viewport]);
+		Self { pass, commands, dynamic_state }
+	}
+
+	/// Record drawing some data, same as [`PassInFrame::draw()`](struct.PassInFrame.html#method.draw).
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to write draw commands to the command buffer.
+	#[inline]
+	pub fn draw<VB, DSC, PC>(
+		mut self,
+		vertex_buffer: VB,
+		descriptor_sets: DSC,
+		push_constants: PC
+	) -> Self
+	where
+		P : VertexSource<VB>,
+		DSC : DescriptorSetsCollection,
+	{
+		self.commands = self.commands.draw(self.pass.pipeline.clone(), &self.dynamic_state, vertex_buffer, descriptor_sets, push_constants).unwrap();
+		self
+	}
+
+	/// Record drawing some indexed vertex data, same as [`PassInFrame::draw_indexed()`](struct.PassInFrame.html#method.draw_indexed).
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to write draw commands to the command buffer.
+	#[inline]
+	pub fn draw_indexed<VB, IB, DSC, PC, I>(
+		mut self,
+		vertex_buffer: VB,
+		index_buffer: IB,
+		descriptor_sets: DSC,
+		push_constants: PC
+	) -> Self
+	where
+		P : VertexSource<VB>,
+		DSC : DescriptorSetsCollection,
+		IB : BufferAccess + TypedBufferAccess<Content = [I]> + Send + Sync + 'static,
+		I : Index + 'static,
+	{
+		self.commands = self.commands.draw_indexed(self.pass.pipeline.clone(), &self.dynamic_state, vertex_buffer, index_buffer, descriptor_sets, push_constants).unwrap();
+		self
+	}
+
+	/// Finish recording the commands.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if fails to build (finalize) the command buffer.
+	pub fn build(self) -> SecondaryCommands {
+		SecondaryCommands { commands: self.commands.build().unwrap() }
+	}
+}
+
+// Release the device after submitting a frame, the next frame waits for the submitted one.
+// If submitting failed (for example with `FlushError::OutOfDate`) the synchronization state is reset instead, so the next frame does not wait for the failed one.
+fn finish_submission(mut device: Device, submitted: Result<FrameFence, FrameFinishError>) -> Result<Device, (Device, FrameFinishError)> {
+	match submitted {
+		Ok(fence) => {
+			let fence = Arc::new(fence);
+			#[cfg(feature = "strict-validation")]
+			{ device.frame_fence = Some(fence.clone()); }
+			device.before_frame = Some(Box::new(fence));
+			Ok(device)
+		},
+		Err(err) => {
+			log_event!(warn, "gaclen: failed to finish the frame: {}", err);
+			device.before_frame = Some(Box::new(vulkano::sync::now(device.logical_device())));
+			Err((device, err))
+		},
+	}
+}
+
+// Dynamic state of a pass drawing to given viewports, scissors are irrelevant for gaclen pipelines.
+fn pass_dynamic_state(viewports: Vec<Viewport>) -> DynamicState {
+	DynamicState { viewports: Some(viewports), .. DynamicState::none() }
+}
+
+impl std::fmt::Display for FrameBeginError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			FrameBeginError::Timeout => write!(fmt, "no swapchain image became available in time"),
+			FrameBeginError::Acquire(err) => write!(fmt, "failed to acquire a swapchain image: {}", err),
+		}
+	}
+}
+impl std::error::Error for FrameBeginError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			FrameBeginError::Timeout => None,
+			FrameBeginError::Acquire(err) => Some(err),
+		}
+	}
+}
+
+impl std::fmt::Display for FrameFinishError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			FrameFinishError::Flush(err) => write!(fmt, "failed to flush the frame: {}", err),
+			FrameFinishError::Commands(err) => write!(fmt, "failed to execute the frame commands: {}", err),
+		}
+	}
+}
+impl std::error::Error for FrameFinishError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			FrameFinishError::Flush(err) => Some(err),
+			FrameFinishError::Commands(err) => Some(err),
+		}
+	}
+}
+
+impl std::fmt::Display for DrawError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			DrawError::IndexBufferMissingUsage => write!(fmt, "the index buffer was not created with index buffer usage"),
+		}
+	}
+}
+impl std::error::Error for DrawError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::graphics::context::Context;
+
+	#[test]
+	fn test_pass_dynamic_state() {
+		let shadow = Viewport { origin: [0.0; 2], dimensions: [512.0; 2], depth_range: 0.0..1.0 };
+		let window = Viewport { origin: [0.0; 2], dimensions: [1280.0, 720.0], depth_range: 0.0..1.0 };
+
+		let mut state = pass_dynamic_state(vec![shadow]);
+		state.line_width = Some(2.0);
+		assert_eq!(state.viewports.as_ref().map(|viewports| viewports[0].dimensions), Some([512.0; 2]));
+		assert_eq!(state.line_width, Some(2.0));
+		state = pass_dynamic_state(vec![window]);
+
+		let viewports = state.viewports.expect("The pass should have viewports");
+		assert_eq!(viewports.len(), 1);
+		assert_eq!(viewports[0].dimensions, [1280.0, 720.0]);
+		assert!(state.scissors.is_none());
+		assert!(state.line_width.is_none());
+	}
+
+	#[test]
+	#[ignore] // requires a Vulkan capable device
+	fn test_finish_submission() {
+		let context = Context::new().unwrap();
+		let mut device = Device::new_headless(&context).unwrap();
+
+		let errors = vec![
+			FrameFinishError::Commands(CommandBufferExecError::OneTimeSubmitAlreadySubmitted),
+			FrameFinishError::Flush(FlushError::OutOfDate),
+		];
+		for error in errors {
+			device.before_frame = None;
+			let (returned, reported) = finish_submission(device, Err(error.clone())).err().expect("the failure should be reported");
+			assert_eq!(reported, error);
+			assert!(returned.before_frame.is_some());
+			device = returned;
+		}
+	}
+}
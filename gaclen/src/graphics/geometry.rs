@@ -0,0 +1,165 @@
+//! Geometry provides generators for common primitive shapes.
+//!
+//! All primitives use the same [`Vertex`](../vertex/struct.Vertex3D.html) layout, which can be used as input to a [`GraphicalPass`](../pass/struct.GraphicalPass.html):
+//! ```
+//! let pass = gaclen::graphics::pass::GraphicalPass::start()
+//! 	.single_buffer_input::<gaclen::graphics::geometry::Vertex>();
+//! ```
+//!
+//! The primitives are centered around the origin, have unit size and use counter-clockwise winding when viewed from outside.
+//!
+//! Each primitive is available both as raw data (`*_data()` functions) and as device-local vertex and index buffers.
+
+use super::buffer::{ImmutableBuffer, create_immutable_index_buffer, create_immutable_vertex_buffer};
+use super::device::Device;
+
+use std::sync::Arc;
+
+use vulkano::memory::DeviceMemoryAllocError;
+
+/// Standard vertex layout used by the primitives.
+pub use super::vertex::Vertex3D as Vertex;
+
+/// Device-local vertex and index buffers of a primitive.
+pub type GeometryBuffers = (Arc<ImmutableBuffer<[Vertex]>>, Arc<ImmutableBuffer<[u32]>>);
+
+/// Create buffers with a unit quad in the XY plane, facing `+Z`.
+pub fn quad(device: &Device) -> Result<GeometryBuffers, DeviceMemoryAllocError> { upload(device, quad_data()) }
+
+/// Create buffers with a unit cube.
+pub fn cube(device: &Device) -> Result<GeometryBuffers, DeviceMemoryAllocError> { upload(device, cube_data()) }
+
+/// Create buffers with a unit-diameter sphere, with poles on the Z axis.
+///
+/// # Panic
+///
+/// - Panics if `rings < 2` or `sectors < 3`.
+pub fn uv_sphere(device: &Device, rings: u32, sectors: u32) -> Result<GeometryBuffers, DeviceMemoryAllocError> { upload(device, uv_sphere_data(rings, sectors)) }
+
+/// Generate vertices and indices of a unit quad in the XY plane, facing `+Z`.
+pub fn quad_data() -> (Vec<Vertex>, Vec<u32>) {
+	let mut vertices = Vec::with_capacity(4);
+	let mut indices = Vec::with_capacity(6);
+	push_face(&mut vertices, &mut indices, [0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+	(vertices, indices)
+}
+
+/// Generate vertices and indices of a unit cube.
+pub fn cube_data() -> (Vec<Vertex>, Vec<u32>) {
+	// (normal, tangent, bitangent), tangent x bitangent == normal
+	const FACES: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+		([ 1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]),
+		([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+		([0.0,  1.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]),
+		([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+		([0.0, 0.0,  1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+		([0.0, 0.0, -1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0]),
+	];
+
+	let mut vertices = Vec::with_capacity(4 * FACES.len());
+	let mut indices = Vec::with_capacity(6 * FACES.len());
+	for (normal, tangent, bitangent) in FACES.iter() {
+		let center = [normal[0] * 0.5, normal[1] * 0.5, normal[2] * 0.5];
+		push_face(&mut vertices, &mut indices, center, *normal, *tangent, *bitangent);
+	};
+	(vertices, indices)
+}
+
+/// Generate vertices and indices of a unit-diameter sphere, with poles on the Z axis.
+///
+/// - `rings` is the number of horizontal slices.
+/// - `sectors` is the number of vertical slices.
+///
+/// # Panic
+///
+/// - Panics if `rings < 2` or `sectors < 3`.
+pub fn uv_sphere_data(rings: u32, sectors: u32) -> (Vec<Vertex>, Vec<u32>) {
+	assert!(rings >= 2, "A sphere requires at least 2 rings.");
+	assert!(sectors >= 3, "A sphere requires at least 3 sectors.");
+
+	let mut vertices = Vec::with_capacity(((rings + 1) * (sectors + 1)) as usize);
+	for ring in 0..=rings {
+		let v = ring as f32 / rings as f32;
+		let theta = std::f32::consts::PI * v;
+		for sector in 0..=sectors {
+			let u = sector as f32 / sectors as f32;
+			let phi = 2.0 * std::f32::consts::PI * u;
+			let normal = [theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()];
+			vertices.push(Vertex {
+				position: [normal[0] * 0.5, normal[1] * 0.5, normal[2] * 0.5],
+				normal,
+				uv: [u, v],
+			});
+		};
+	};
+
+	let mut indices = Vec::with_capacity((rings * sectors * 6) as usize);
+	for ring in 0..rings {
+		for sector in 0..sectors {
+			let current = ring * (sectors + 1) + sector;
+			let below = current + sectors + 1;
+			indices.extend_from_slice(&[current, below, current + 1, current + 1, below, below + 1]);
+		};
+	};
+
+	(vertices, indices)
+}
+
+fn push_face(vertices: &mut Vec<Vertex>, indices: &mut Vec<u32>, center: [f32; 3], normal: [f32; 3], tangent: [f32; 3], bitangent: [f32; 3]) {
+	let first = vertices.len() as u32;
+	// (tangent offset, bitangent offset, uv) in counter-clockwise order
+	const CORNERS: [(f32, f32, [f32; 2]); 4] = [
+		(-0.5, -0.5, [0.0, 1.0]),
+		( 0.5, -0.5, [1.0, 1.0]),
+		( 0.5,  0.5, [1.0, 0.0]),
+		(-0.5,  0.5, [0.0, 0.0]),
+	];
+	for (t, b, uv) in CORNERS.iter() {
+		let mut position = center;
+		for (coordinate, (tangent_axis, bitangent_axis)) in position.iter_mut().zip(tangent.iter().zip(bitangent.iter())) {
+			*coordinate += tangent_axis * t + bitangent_axis * b;
+		};
+		vertices.push(Vertex { position, normal, uv: *uv });
+	};
+	indices.extend_from_slice(&[first, first + 1, first + 2, first + 2, first + 3, first]);
+}
+
+fn upload(device: &Device, (vertices, indices): (Vec<Vertex>, Vec<u32>)) -> Result<GeometryBuffers, DeviceMemoryAllocError> {
+	let vertices = create_immutable_vertex_buffer(device, vertices.into_iter())?;
+	let indices = create_immutable_index_buffer(device, indices.into_iter())?;
+	Ok((vertices, indices))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_valid((vertices, indices): (Vec<Vertex>, Vec<u32>)) {
+		assert_eq!(indices.len() % 3, 0);
+		assert!(indices.iter().all(|&index| (index as usize) < vertices.len()));
+		for vertex in vertices {
+			let length = vertex.normal.iter().map(|x| x * x).sum::<f32>().sqrt();
+			assert!((length - 1.0).abs() < 1e-5);
+		};
+	}
+
+	#[test]
+	fn test_primitives_are_valid() {
+		assert_valid(quad_data());
+		assert_valid(cube_data());
+		assert_valid(uv_sphere_data(8, 16));
+	}
+
+	#[test]
+	fn test_cube_faces_point_outwards() {
+		let (vertices, indices) = cube_data();
+		for triangle in indices.chunks(3) {
+			let [a, b, c] = [vertices[triangle[0] as usize], vertices[triangle[1] as usize], vertices[triangle[2] as usize]];
+			let ab = [b.position[0] - a.position[0], b.position[1] - a.position[1], b.position[2] - a.position[2]];
+			let ac = [c.position[0] - a.position[0], c.position[1] - a.position[1], c.position[2] - a.position[2]];
+			let cross = [ab[1] * ac[2] - ab[2] * ac[1], ab[2] * ac[0] - ab[0] * ac[2], ab[0] * ac[1] - ab[1] * ac[0]];
+			let dot: f32 = cross.iter().zip(a.normal.iter()).map(|(x, y)| x * y).sum();
+			assert!(dot > 0.0);
+		};
+	}
+}
@@ -0,0 +1,205 @@
+//! Graph orders the passes of a frame by the images they write and read.
+//!
+//! Passes that draw to images used by other passes (such as a shadow map sampled by the main pass, or a G-buffer read by a lighting pass) have to be recorded in the right order.
+//! A [`FrameGraph`](struct.FrameGraph.html) records the passes and their dependencies once and [executes](struct.FrameGraph.html#method.execute) them in order every frame:
+//! ```ignore
+//! let mut graph = FrameGraph::new();
+//! let shadow_map = graph.add_resource();
+//! graph.add_pass(ScenePass::Albedo, &[shadow_map], &[]);
+//! graph.add_pass(ScenePass::Shadow, &[], &[shadow_map]);
+//! // In the main loop, the shadow pass is recorded first:
+//! let frame = graph.execute(frame, |frame, pass| match pass {
+//! 	ScenePass::Shadow => frame.begin_pass(&shadow_pass, ..).draw(..).finish_pass(),
+//! 	ScenePass::Albedo => frame.begin_pass(&albedo_pass, ..).draw(..).finish_pass(),
+//! });
+//! ```
+//!
+//! Every pass reading a resource is ordered after all passes writing it.
+//! Multiple passes writing the same resource (such as a pass that reads and writes it) are ordered as they were added, otherwise passes keep the order they were added in.
+//!
+//! The graph only orders the passes, it does not manage the images.
+//! Pipeline barriers between passes (for example between drawing to an image and sampling it) are inserted automatically when the commands are recorded, as long as the images are used through framebuffers and descriptor sets.
+
+use super::frame::Frame;
+
+/// Passes of a frame along with the resources they write and read, see [module documentation](index.html).
+#[derive(Clone, Debug)]
+pub struct FrameGraph<T> {
+	nodes: Vec<Node<T>>,
+	resource_count: usize,
+}
+
+/// A resource (typically an image) passes of a [`FrameGraph`](struct.FrameGraph.html) depend on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResourceId(usize);
+
+/// A pass of a [`FrameGraph`](struct.FrameGraph.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PassId(usize);
+
+/// Error during ordering the passes of a [`FrameGraph`](struct.FrameGraph.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphError {
+	/// The passes depend on each other, so there is no valid order.
+	///
+	/// Contains the passes that could not be ordered.
+	Cycle(Vec<PassId>),
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+	value: T,
+	reads: Vec<ResourceId>,
+	writes: Vec<ResourceId>,
+}
+
+impl<T> FrameGraph<T> {
+	/// Create an empty graph.
+	pub fn new() -> Self { Self { nodes: Vec::new(), resource_count: 0 } }
+
+	/// Add a resource passes can depend on.
+	pub fn add_resource(&mut self) -> ResourceId {
+		self.resource_count += 1;
+		ResourceId(self.resource_count - 1)
+	}
+
+	/// Add a pass that reads and writes given resources.
+	///
+	/// The `value` identifies the pass when the graph is [executed](#method.execute).
+	///
+	/// # Panic.
+	///
+	/// - Panics if any of the resources was not added to this graph.
+	pub fn add_pass(&mut self, value: T, reads: &[ResourceId], writes: &[ResourceId]) -> PassId {
+		assert!(reads.iter().chain(writes.iter()).all(|resource| resource.0 < self.resource_count), "The resource was not added to this graph.");
+		self.nodes.push(Node { value, reads: reads.to_vec(), writes: writes.to_vec() });
+		PassId(self.nodes.len() - 1)
+	}
+
+	/// Get the value of given pass.
+	#[inline]
+	pub fn pass(&self, id: PassId) -> &T { &self.nodes[id.0].value }
+
+	/// Get the number of passes in the graph.
+	#[inline]
+	pub fn pass_count(&self) -> usize { self.nodes.len() }
+
+	/// Order the passes so that each one is after the passes it depends on.
+	///
+	/// Independent passes keep the order they were added in.
+	pub fn order(&self) -> Result<Vec<PassId>, GraphError> {
+		let mut dependents = vec![Vec::new(); self.nodes.len()];
+		let mut dependency_counts = vec![0usize; self.nodes.len()];
+		let mut add_dependency = |before: usize, after: usize| {
+			if before != after && !dependents[before].contains(&after) {
+				dependents[before].push(after);
+				dependency_counts[after] += 1;
+			}
+		};
+
+		for resource in (0..self.resource_count).map(ResourceId) {
+			let writers: Vec<usize> = (0..self.nodes.len()).filter(|&index| self.nodes[index].writes.contains(&resource)).collect();
+			for pair in writers.windows(2) {
+				add_dependency(pair[0], pair[1]);
+			};
+			for reader in (0..self.nodes.len()).filter(|&index| self.nodes[index].reads.contains(&resource)) {
+				match writers.contains(&reader) {
+					// a pass that also writes the resource only reads what was written before it
+					true => writers.iter().take_while(|&&writer| writer != reader).for_each(|&writer| add_dependency(writer, reader)),
+					false => writers.iter().for_each(|&writer| add_dependency(writer, reader)),
+				};
+			};
+		};
+
+		let mut order = Vec::with_capacity(self.nodes.len());
+		let mut ordered = vec![false; self.nodes.len()];
+		while let Some(next) = (0..self.nodes.len()).find(|&index| !ordered[index] && dependency_counts[index] == 0) {
+			ordered[next] = true;
+			order.push(PassId(next));
+			for &dependent in dependents[next].iter() {
+				dependency_counts[dependent] -= 1;
+			};
+		};
+
+		match order.len() == self.nodes.len() {
+			true => Ok(order),
+			false => Err(GraphError::Cycle((0..self.nodes.len()).filter(|&index| !ordered[index]).map(PassId).collect())),
+		}
+	}
+
+	/// Record the passes of a frame in dependency order.
+	///
+	/// The `record` is invoked with the value of each pass and should [begin](../frame/struct.Frame.html#method.begin_pass) and [finish](../frame/struct.PassInFrame.html#method.finish_pass) it.
+	///
+	/// # Panic.
+	///
+	/// - Panics if the passes can not be ordered, use [`FrameGraph::order()`](#method.order) to check beforehand.
+	pub fn execute(&self, frame: Frame, mut record: impl FnMut(Frame, &T) -> Frame) -> Frame {
+		let order = self.order().expect("The passes of the graph can not be ordered.");
+		order.into_iter().fold(frame, |frame, id| record(frame, &self.nodes[id.0].value))
+	}
+}
+
+impl<T> Default for FrameGraph<T> {
+	fn default() -> Self { Self::new() }
+}
+
+impl std::fmt::Display for GraphError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			GraphError::Cycle(passes) => write!(fmt, "the passes {:?} depend on each other", passes),
+		}
+	}
+}
+impl std::error::Error for GraphError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn values<T: Copy>(graph: &FrameGraph<T>) -> Vec<T> {
+		graph.order().unwrap().into_iter().map(|id| *graph.pass(id)).collect()
+	}
+
+	#[test]
+	fn test_writers_before_readers() {
+		let mut graph = FrameGraph::new();
+		let shadow_map = graph.add_resource();
+		graph.add_pass("albedo", &[shadow_map], &[]);
+		graph.add_pass("shadow", &[], &[shadow_map]);
+		assert_eq!(values(&graph), ["shadow", "albedo"]);
+	}
+
+	#[test]
+	fn test_chain() {
+		let mut graph = FrameGraph::new();
+		let g_buffer = graph.add_resource();
+		let lit = graph.add_resource();
+		graph.add_pass("post", &[lit], &[]);
+		graph.add_pass("ui", &[], &[]);
+		graph.add_pass("lighting", &[g_buffer], &[lit]);
+		graph.add_pass("geometry", &[], &[g_buffer]);
+		assert_eq!(values(&graph), ["ui", "geometry", "lighting", "post"]);
+	}
+
+	#[test]
+	fn test_read_modify_write() {
+		let mut graph = FrameGraph::new();
+		let image = graph.add_resource();
+		graph.add_pass("present", &[image], &[]);
+		graph.add_pass("draw", &[], &[image]);
+		graph.add_pass("blur", &[image], &[image]);
+		assert_eq!(values(&graph), ["draw", "blur", "present"]);
+	}
+
+	#[test]
+	fn test_cycle() {
+		let mut graph = FrameGraph::new();
+		let a = graph.add_resource();
+		let b = graph.add_resource();
+		graph.add_pass("independent", &[], &[]);
+		let first = graph.add_pass("first", &[a], &[b]);
+		let second = graph.add_pass("second", &[b], &[a]);
+		assert_eq!(graph.order(), Err(GraphError::Cycle(vec![first, second])));
+	}
+}
@@ -1,11 +1,24 @@
 //! Currently **gaclen** simply uses `vulkano` images directly.
+//!
+//! # Color spaces.
+//!
+//! Textures that store colors (albedo, UI, sprites) are typically *sRGB*-encoded and should use an *sRGB* format, so they are decoded when sampled.
+//! Textures that store data (normal, roughness or metalness maps) must use a linear (`Unorm`) format instead, otherwise the lighting is visibly wrong.
+//! [`create_texture()`](fn.create_texture.html) selects the format based on provided [`ColorSpace`](../color/enum.ColorSpace.html).
 
-use super::device::Device;
+use super::color::ColorSpace;
+use super::device::{Device, QueueOperation, SubmitError};
+use super::format::{block_size, bytes_per_pixel, has_depth, supports_sampling};
 
 use std::sync::Arc;
 
+use vulkano::buffer::{BufferUsage, CpuAccessibleBuffer};
 use vulkano::sync::GpuFuture;
 use vulkano::format::{AcceptsPixels, FormatDesc};
+use vulkano::image::{ImageAccess, ImageLayout, ImageUsage, ImageViewAccess, MipmapsCount};
+use vulkano::image::sys::{UnsafeImageView, ViewType};
+use vulkano::memory::DeviceMemoryAllocError;
+use vulkano::OomError;
 
 pub use vulkano::format::{Format};
 pub use vulkano::image::{AttachmentImage, Dimensions, ImmutableImage, ImageCreationError};
@@ -14,11 +27,14 @@ pub use vulkano::pipeline::depth_stencil::Compare as CompareOp;
 
 /// Create an [`ImmutableImage`](struct.ImmutableImage.html) from a data iterator.
 /// 
-/// Builds an intermediate memory-mapped buffer, writes data to it, builds a copy (upload) command buffer and executes it.
+/// Builds an intermediate memory-mapped buffer, writes data to it, builds a copy (upload) command buffer and executes it on the transfer queue, blocking until it is finished.
+/// The image is shared between the queue families of the device, so it can be used on the graphics queue right away.
 /// 
 /// # Panic.
 /// 
 /// - Panics if fails to submit the copy command buffer.
+/// 
+/// Use [`Device::upload_immutable_image()`](../device/struct.Device.html#method.upload_immutable_image) to avoid blocking on the upload.
 pub fn create_immutable_image_from_iter<P, I, F>(device: &Device, data_iterator: I, dimensions: Dimensions, format: F)
 -> Result<Arc<ImmutableImage<F>>, ImageCreationError>
 where
@@ -27,16 +43,648 @@ where
 	I : ExactSizeIterator<Item = P>,
 	Format: AcceptsPixels<P>,
 {
-	let (image, future) = ImmutableImage::from_iter(data_iterator, dimensions, format, device.transfer_queue.clone())?;
+	upload_image(device, data_iterator, dimensions, format)
+}
+
+/// Create a 2D texture from decoded 8-bit RGBA pixels.
+/// 
+/// Use `ColorSpace::Srgb` for color textures and `ColorSpace::Linear` for data textures, see [color spaces](index.html#color-spaces).
+/// 
+/// # Panic.
+/// 
+/// - Panics if fails to submit the copy command buffer.
+pub fn create_texture(device: &Device, pixels: impl ExactSizeIterator<Item = u8>, dimensions: [u32; 2], color_space: ColorSpace)
+-> Result<Arc<ImmutableImage<Format>>, ImageCreationError>
+{
+	let dimensions = Dimensions::Dim2d { width: dimensions[0], height: dimensions[1] };
+	create_immutable_image_from_iter(device, pixels, dimensions, color_space.rgba8_format())
+}
+
+/// Description of an image created by [`create_sampled_image()`](fn.create_sampled_image.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageDescriptor {
+	/// Dimensions of the first (largest) mip level.
+	pub dimensions: [u32; 2],
+	/// Number of mip levels, each one half the size of the previous one.
+	pub mip_levels: u32,
+	/// Number of array layers, an image with more than 1 layer is a 2D array.
+	pub array_layers: u32,
+	/// Format of the image, compressed formats are not supported.
+	pub format: Format,
+}
+
+impl ImageDescriptor {
+	/// Create a descriptor of a 2D image with a single mip level and array layer.
+	pub fn new(dimensions: [u32; 2], format: Format) -> Self { Self { dimensions, mip_levels: 1, array_layers: 1, format } }
+
+	/// Set the number of mip levels.
+	pub fn mip_levels(mut self, levels: u32) -> Self { self.mip_levels = levels; self }
+	/// Set the number of array layers.
+	pub fn array_layers(mut self, layers: u32) -> Self { self.array_layers = layers; self }
+
+	/// Get the maximum number of mip levels for the dimensions of the image (a full mip chain down to 1x1).
+	pub fn max_mip_levels(&self) -> u32 { 32 - self.dimensions[0].max(self.dimensions[1]).max(1).leading_zeros() }
+
+	/// Get the dimensions of given mip level.
+	pub fn level_dimensions(&self, level: u32) -> [u32; 2] {
+		[(self.dimensions[0] >> level).max(1), (self.dimensions[1] >> level).max(1)]
+	}
+
+	/// Get the size (in bytes) of the data of the whole image, `None` if the format has no per-pixel size.
+	pub fn data_size(&self) -> Option<usize> {
+		let bytes_per_pixel = bytes_per_pixel(self.format)?;
+		Some((0..self.mip_levels).map(|level| self.level_size(level, bytes_per_pixel)).sum())
+	}
+
+	// Get the size (in bytes) of the data of all array layers of given mip level.
+	fn level_size(&self, level: u32, bytes_per_pixel: usize) -> usize {
+		let [width, height] = self.level_dimensions(level);
+		width as usize * height as usize * self.array_layers as usize * bytes_per_pixel
+	}
+}
+
+/// Error during creation of a sampled image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SampledImageError {
+	/// The format is compressed or can not be sampled by the device.
+	UnsupportedFormat(Format),
+	/// The number of mip levels is 0 or exceeds the maximum for the dimensions of the image.
+	InvalidMipLevels(u32),
+	/// The number of array layers is 0.
+	InvalidArrayLayers,
+	/// The size of the data does not match the description of the image.
+	SizeMismatch {
+		/// Size (in bytes) of the whole image.
+		expected: usize,
+		/// Size (in bytes) of the provided data.
+		actual: usize,
+	},
+	/// Error during the allocation of the intermediate buffer.
+	Allocation(DeviceMemoryAllocError),
+	/// Error during the creation of the image.
+	Creation(ImageCreationError),
+}
+
+/// Create a sampled [`ImmutableImage`](struct.ImmutableImage.html) with explicit mip levels and array layers, such as a texture array.
+/// 
+/// The data should contain tightly packed pixels of every mip level in order, each level containing all array layers in order.
+/// The mip levels are uploaded as is, they are not generated.
+/// 
+/// # Panic.
+/// 
+/// - Panics if fails to create the copy command buffer.
+/// - Panics if fails to submit the copy command buffer.
+pub fn create_sampled_image(device: &Device, descriptor: ImageDescriptor, data: &[u8])
+-> Result<Arc<ImmutableImage<Format>>, SampledImageError>
+{
+	let format = descriptor.format;
+	let bytes_per_pixel = match bytes_per_pixel(format) {
+		Some(bytes) if supports_sampling(device, format) => bytes,
+		_ => return Err(SampledImageError::UnsupportedFormat(format)),
+	};
+	if descriptor.mip_levels == 0 || descriptor.mip_levels > descriptor.max_mip_levels() {
+		return Err(SampledImageError::InvalidMipLevels(descriptor.mip_levels));
+	}
+	if descriptor.array_layers == 0 { return Err(SampledImageError::InvalidArrayLayers); }
 
-	// TODO: handle synchronization between separate queues in a performant way
-	future.flush().unwrap();
+	let expected = (0..descriptor.mip_levels).map(|level| descriptor.level_size(level, bytes_per_pixel)).sum();
+	if data.len() != expected {
+		return Err(SampledImageError::SizeMismatch { expected, actual: data.len() });
+	}
 
-	// let time: Box<dyn GpuFuture> = match self.before_frame.take() {
-	// 	Some(time) => Box::new(time.join(future)),
-	// 	None => Box::new(future),
-	// };
-	// self.before_frame = Some(time);
+	let [width, height] = descriptor.dimensions;
+	let dimensions = match descriptor.array_layers {
+		1 => Dimensions::Dim2d { width, height },
+		array_layers => Dimensions::Dim2dArray { width, height, array_layers },
+	};
+	let usage = ImageUsage { transfer_destination: true, sampled: true, .. ImageUsage::none() };
+	let (image, initialization) = ImmutableImage::uninitialized(
+		device.logical_device(),
+		dimensions,
+		format,
+		MipmapsCount::Specific(descriptor.mip_levels),
+		usage,
+		ImageLayout::ShaderReadOnlyOptimal,
+		device.sharing_families(),
+	)?;
+	// Every mip level is copied separately to the same image.
+	let initialization = Arc::new(initialization);
+
+	let source = CpuAccessibleBuffer::from_iter(device.logical_device(), BufferUsage::transfer_source(), false, data.iter().cloned())?;
+	device.upload_blocking(|mut commands| {
+		let mut offset = 0;
+		for level in 0..descriptor.mip_levels {
+			let size = descriptor.level_size(level, bytes_per_pixel);
+			let [width, height] = descriptor.level_dimensions(level);
+			let level_data = source.clone().into_buffer_slice().slice(offset .. offset + size).unwrap();
+			commands = commands.copy_buffer_to_image_dimensions(level_data, initialization.clone(), [0, 0, 0], [width, height, 1], 0, descriptor.array_layers, level).unwrap();
+			offset += size;
+		};
+		commands
+	});
 
 	Ok(image)
 }
+
+/// Error during creation of a block-compressed image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompressedImageError {
+	/// The format is not block-compressed or can not be sampled by the device.
+	UnsupportedFormat(Format),
+	/// The size of the data does not match the dimensions of the image.
+	SizeMismatch {
+		/// Size (in bytes) of the whole image.
+		expected: usize,
+		/// Size (in bytes) of the provided data.
+		actual: usize,
+	},
+	/// Error during the creation of the image.
+	Creation(ImageCreationError),
+}
+
+/// Create an [`ImmutableImage`](struct.ImmutableImage.html) of a block-compressed format (such as *BC7* or *ASTC*) from pre-compressed data.
+/// 
+/// The data should contain tightly packed blocks of the first mip level of every array layer.
+/// Compressed images use a fraction of memory and bandwidth of uncompressed ones, which makes them preferable for most textures.
+/// See [`format::block_size()`](../format/fn.block_size.html) for recognized formats.
+/// 
+/// # Panic.
+/// 
+/// - Panics if fails to submit the copy command buffer.
+pub fn create_immutable_compressed(device: &Device, data: &[u8], dimensions: Dimensions, format: Format)
+-> Result<Arc<ImmutableImage<Format>>, CompressedImageError>
+{
+	let ([block_width, block_height], block_bytes) = match block_size(format) {
+		Some(size) if supports_sampling(device, format) => size,
+		_ => return Err(CompressedImageError::UnsupportedFormat(format)),
+	};
+
+	let blocks_x = (dimensions.width() + block_width - 1) / block_width;
+	let blocks_y = (dimensions.height() + block_height - 1) / block_height;
+	let expected = blocks_x as usize * blocks_y as usize * dimensions.depth() as usize * dimensions.array_layers() as usize * block_bytes;
+	if data.len() != expected {
+		return Err(CompressedImageError::SizeMismatch { expected, actual: data.len() });
+	}
+
+	Ok(upload_image(device, data.iter().cloned(), dimensions, format)?)
+}
+
+/// Create a [`Sampler`](struct.Sampler.html) with linear filtering, repeating addressing and anisotropic filtering.
+/// 
+/// `max_anisotropy` should be larger than `1.0` and may not exceed the limit of the device.
+/// Requires the `sampler_anisotropy` [feature](../device/struct.DeviceFeatures.html) to be enabled, which is guaranteed if it was [required](../device/struct.DeviceConfig.html#structfield.required_features) during device creation.
+/// Fails with `SamplerCreationError::SamplerAnisotropyFeatureNotEnabled` otherwise.
+pub fn create_anisotropic_sampler(device: &Device, max_anisotropy: f32) -> Result<Arc<Sampler>, SamplerCreationError> {
+	Sampler::new(
+		device.logical_device(),
+		Filter::Linear,
+		Filter::Linear,
+		MipmapMode::Linear,
+		SamplerAddressMode::Repeat,
+		SamplerAddressMode::Repeat,
+		SamplerAddressMode::Repeat,
+		0.0,
+		max_anisotropy,
+		0.0,
+		1000.0,
+	)
+}
+
+/// Create a [`Sampler`](struct.Sampler.html) with linear filtering between pixels and mip levels (trilinear filtering).
+/// 
+/// The `address_mode` is used for all coordinates.
+/// A positive `lod_bias` selects smaller mip levels (blurrier, less aliasing), a negative one selects larger ones (sharper).
+/// All mip levels of the sampled image are used, see [`create_trilinear_sampler_with_lod_range()`](fn.create_trilinear_sampler_with_lod_range.html) to limit them.
+/// Fails with `SamplerCreationError::MipLodBiasLimitExceeded` if `lod_bias` exceeds the limit of the device.
+#[inline]
+pub fn create_trilinear_sampler(device: &Device, address_mode: SamplerAddressMode, lod_bias: f32) -> Result<Arc<Sampler>, SamplerCreationError> {
+	create_trilinear_sampler_with_lod_range(device, address_mode, lod_bias, 0.0..1000.0)
+}
+
+/// Create a [`Sampler`](struct.Sampler.html) with trilinear filtering that only uses mip levels in `mip_lod_range`.
+/// 
+/// Same as [`create_trilinear_sampler()`](fn.create_trilinear_sampler.html), but the level of detail is clamped to the range, for example `0.0..0.0` always samples the full resolution level and `2.0..1000.0` skips the 2 largest levels.
+/// 
+/// # Panic.
+/// 
+/// - Panics if `mip_lod_range` is negative or its end is less than its start.
+pub fn create_trilinear_sampler_with_lod_range(device: &Device, address_mode: SamplerAddressMode, lod_bias: f32, mip_lod_range: std::ops::Range<f32>) -> Result<Arc<Sampler>, SamplerCreationError> {
+	SamplerDescriptor::trilinear(address_mode)
+		.lod_bias(lod_bias)
+		.with_min_lod(mip_lod_range.start)
+		.with_max_lod(mip_lod_range.end)
+		.create(device)
+}
+
+/// Description of a [`Sampler`](struct.Sampler.html), used to create variants of it.
+///
+/// Samplers are immutable once created, but they are cheap, so changing a parameter (such as the level of detail range of a streamed texture) is done by creating a new one:
+/// ```ignore
+/// let descriptor = SamplerDescriptor::trilinear(SamplerAddressMode::Repeat);
+/// // Only the 2 smallest levels of a 10-level texture are loaded so far.
+/// let streaming = descriptor.with_min_lod(8.0).create(&device)?;
+/// // All levels are loaded.
+/// let resident = descriptor.create(&device)?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SamplerDescriptor {
+	/// Filter used when the image is magnified.
+	pub mag_filter: Filter,
+	/// Filter used when the image is minified.
+	pub min_filter: Filter,
+	/// Filtering between mip levels.
+	pub mipmap_mode: MipmapMode,
+	/// Addressing of the U, V and W coordinates.
+	pub address_mode: [SamplerAddressMode; 3],
+	/// Bias added to the level of detail, a positive one selects smaller mip levels.
+	pub lod_bias: f32,
+	/// Maximum anisotropy, `1.0` disables anisotropic filtering.
+	pub max_anisotropy: f32,
+	/// Smallest level of detail used, the largest mip level that is sampled.
+	pub min_lod: f32,
+	/// Largest level of detail used, the smallest mip level that is sampled.
+	pub max_lod: f32,
+}
+
+impl SamplerDescriptor {
+	/// Describe a sampler with trilinear filtering, using all mip levels and given `address_mode` for all coordinates.
+	pub fn trilinear(address_mode: SamplerAddressMode) -> Self {
+		Self {
+			mag_filter: Filter::Linear,
+			min_filter: Filter::Linear,
+			mipmap_mode: MipmapMode::Linear,
+			address_mode: [address_mode; 3],
+			lod_bias: 0.0,
+			max_anisotropy: 1.0,
+			min_lod: 0.0,
+			max_lod: 1000.0,
+		}
+	}
+
+	/// Set the bias added to the level of detail.
+	pub fn lod_bias(mut self, bias: f32) -> Self { self.lod_bias = bias; self }
+	/// Set the maximum anisotropy, requires the `sampler_anisotropy` [feature](../device/struct.DeviceFeatures.html) if larger than `1.0`.
+	pub fn max_anisotropy(mut self, anisotropy: f32) -> Self { self.max_anisotropy = anisotropy; self }
+	/// Set the smallest level of detail, for example `2.0` skips the 2 largest mip levels.
+	///
+	/// Used to sample only the loaded levels of a texture whose largest levels are still being streamed in.
+	pub fn with_min_lod(mut self, lod: f32) -> Self { self.min_lod = lod; self }
+	/// Set the largest level of detail, for example `0.0` samples only the largest mip level.
+	pub fn with_max_lod(mut self, lod: f32) -> Self { self.max_lod = lod; self }
+
+	/// Create a sampler with the described parameters.
+	///
+	/// Fails with `SamplerCreationError::MipLodBiasLimitExceeded` or `SamplerCreationError::AnisotropyLimitExceeded` if the parameters exceed the limits of the device.
+	///
+	/// # Panic.
+	///
+	/// - Panics if the level of detail range is negative or `max_lod` is less than `min_lod`.
+	pub fn create(&self, device: &Device) -> Result<Arc<Sampler>, SamplerCreationError> {
+		assert!(self.min_lod >= 0.0, "The level of detail range should not be negative.");
+		assert!(self.min_lod <= self.max_lod, "The level of detail range should be ordered.");
+
+		Sampler::new(
+			device.logical_device(),
+			self.mag_filter,
+			self.min_filter,
+			self.mipmap_mode,
+			self.address_mode[0],
+			self.address_mode[1],
+			self.address_mode[2],
+			self.lod_bias,
+			self.max_anisotropy,
+			self.min_lod,
+			self.max_lod,
+		)
+	}
+}
+
+/// Create an [`AttachmentImage`](struct.AttachmentImage.html) that can be drawn to as a depth attachment and sampled afterwards, such as a shadow map.
+/// 
+/// Fails with `ImageCreationError::FormatNotSupported` if the format has no depth component.
+pub fn create_depth_target(device: &Device, dimensions: [u32; 2], format: Format) -> Result<Arc<AttachmentImage>, ImageCreationError> {
+	if !has_depth(format) { return Err(ImageCreationError::FormatNotSupported) };
+
+	let usage = ImageUsage { sampled: true, depth_stencil_attachment: true, .. ImageUsage::none() };
+	AttachmentImage::with_usage(device.logical_device(), dimensions, format, usage)
+}
+
+/// Create a depth-comparing [`Sampler`](struct.Sampler.html) with given filter and addressing.
+/// 
+/// The sampled value is `1.0` if the `compare` of the reference value and the stored depth succeeds and `0.0` otherwise.
+/// Uses nearest mipmap selection, no anisotropy and `0.0..1.0` level of detail range, which suits single-level depth images.
+/// The `address_mode` is used for all coordinates, use `SamplerAddressMode::ClampToBorder` to control the result of sampling outside the image.
+pub fn create_depth_compare_sampler(device: &Device, filter: Filter, address_mode: SamplerAddressMode, compare: CompareOp) -> Result<Arc<Sampler>, SamplerCreationError> {
+	Sampler::compare(
+		device.logical_device(),
+		filter,
+		filter,
+		MipmapMode::Nearest,
+		address_mode,
+		address_mode,
+		address_mode,
+		0.0,
+		1.0,
+		0.0,
+		1.0,
+		compare,
+	)
+}
+
+/// Create a depth-comparing [`Sampler`](struct.Sampler.html) for sampling shadow maps.
+/// 
+/// Uses linear filtering (for hardware percentage-closer filtering) and treats samples outside the image as lit.
+/// The sampled value is `1.0` if the `compare` of the reference value and the stored depth succeeds and `0.0` otherwise.
+/// Use [`create_depth_compare_sampler()`](fn.create_depth_compare_sampler.html) for other filtering or addressing.
+pub fn create_shadow_sampler(device: &Device, compare: CompareOp) -> Result<Arc<Sampler>, SamplerCreationError> {
+	let address_mode = SamplerAddressMode::ClampToBorder(BorderColor::FloatOpaqueWhite);
+	create_depth_compare_sampler(device, Filter::Linear, address_mode, compare)
+}
+
+/// Blit (copy with scaling and format conversion) the whole source image onto the whole destination image.
+/// 
+/// Builds a command buffer for blitting the images and executes it after `before`, returning the future of its completion to allow chaining.
+/// Use `vulkano::sync::now()` as `before` if there is nothing to wait for.
+/// 
+/// # Notes
+/// 
+/// - The source image should have `ImageUsage::transfer_source` set to true.
+/// - The destination image should have `ImageUsage::transfer_destination` set to true.
+/// - Only the first mip level and array layer are blitted.
+/// 
+/// # Panic
+/// 
+/// - Panics if fails to create the command buffer.
+/// - Panics if fails to build (finalize) the command buffer.
+pub fn blit<F, S, D>(device: &Device, before: F, source: S, destination: D, filter: Filter) -> Result<Box<dyn GpuFuture>, SubmitError>
+where
+	F : GpuFuture + 'static,
+	S : ImageAccess + Send + Sync + 'static,
+	D : ImageAccess + Send + Sync + 'static,
+{
+	let source_corner = image_corner(&source);
+	let destination_corner = image_corner(&destination);
+	let future = device.submit_after(before, QueueOperation::Graphics, |builder|
+		builder.blit_image(source, [0, 0, 0], source_corner, 0, 0, destination, [0, 0, 0], destination_corner, 0, 0, 1, filter).unwrap()
+	)?;
+	Ok(Box::new(future))
+}
+
+/// Copy the source image to the destination image without any conversion.
+/// 
+/// Builds a command buffer for copying the images and executes it after `before`, returning the future of its completion to allow chaining.
+/// Use `vulkano::sync::now()` as `before` if there is nothing to wait for.
+/// 
+/// # Notes
+/// 
+/// - The source image should have `ImageUsage::transfer_source` set to true.
+/// - The destination image should have `ImageUsage::transfer_destination` set to true.
+/// - The formats of the images should be compatible.
+/// - If the sizes of images are not equal the copied region is the intersection of the two.
+/// - Only the first mip level and array layer are copied.
+/// 
+/// # Panic
+/// 
+/// - Panics if fails to create the command buffer.
+/// - Panics if fails to build (finalize) the command buffer.
+pub fn copy<F, S, D>(device: &Device, before: F, source: S, destination: D) -> Result<Box<dyn GpuFuture>, SubmitError>
+where
+	F : GpuFuture + 'static,
+	S : ImageAccess + Send + Sync + 'static,
+	D : ImageAccess + Send + Sync + 'static,
+{
+	let extent = {
+		let (source, destination) = (source.dimensions(), destination.dimensions());
+		[
+			source.width().min(destination.width()),
+			source.height().min(destination.height()),
+			source.depth().min(destination.depth()),
+		]
+	};
+	let future = device.submit_after(before, QueueOperation::Graphics, |builder|
+		builder.copy_image(source, [0, 0, 0], 0, 0, destination, [0, 0, 0], 0, 0, extent, 1).unwrap()
+	)?;
+	Ok(Box::new(future))
+}
+
+/// A view of a single mip level and array layer of an image, used to render to a part of it.
+///
+/// Can be added to a framebuffer like any other attachment, see [`FramebufferBuilder::add_view()`](../pass/struct.FramebufferBuilder.html#method.add_view).
+/// Useful for rendering to faces of a cubemap, levels of a mip chain or layers of a texture array.
+pub struct ImageSlice<I> {
+	image: I,
+	view: UnsafeImageView,
+	dimensions: [u32; 2],
+	mip_level: u32,
+	array_layer: u32,
+}
+
+/// Error during creation of an [`ImageSlice`](struct.ImageSlice.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImageSliceError {
+	/// The image does not have the requested mip level.
+	MissingMipLevel {
+		/// The requested mip level.
+		level: u32,
+		/// Number of mip levels of the image.
+		levels: u32,
+	},
+	/// The image does not have the requested array layer.
+	MissingArrayLayer {
+		/// The requested array layer.
+		layer: u32,
+		/// Number of array layers of the image.
+		layers: u32,
+	},
+	/// Not enough memory to create the view.
+	OutOfMemory(OomError),
+}
+
+impl<I> ImageSlice<I>
+where
+	I : ImageAccess + ImageViewAccess,
+{
+	/// Create a view of given mip level and array layer of the image.
+	///
+	/// Fails if the image does not have the requested level or layer.
+	pub fn new(image: I, mip_level: u32, array_layer: u32) -> Result<Self, ImageSliceError> {
+		let (view, dimensions) = {
+			let inner = image.inner();
+			let dimensions = inner.image.dimensions();
+			let dimensions = slice_dimensions([dimensions.width(), dimensions.height()], inner.num_mipmap_levels as u32, inner.num_layers as u32, mip_level, array_layer)?;
+			let first_level = inner.first_mipmap_level as u32 + mip_level;
+			let first_layer = inner.first_layer as u32 + array_layer;
+			// the ranges are validated against the image above
+			let view = unsafe { UnsafeImageView::raw(inner.image, ViewType::Dim2d, first_level .. first_level + 1, first_layer .. first_layer + 1)? };
+			(view, dimensions)
+		};
+		Ok(Self { image, view, dimensions, mip_level, array_layer })
+	}
+
+	/// Get the image the slice is a part of.
+	#[inline]
+	pub fn image(&self) -> &I { &self.image }
+	/// Get the mip level of the slice.
+	#[inline]
+	pub fn mip_level(&self) -> u32 { self.mip_level }
+	/// Get the array layer of the slice.
+	#[inline]
+	pub fn array_layer(&self) -> u32 { self.array_layer }
+}
+
+unsafe impl<I> ImageViewAccess for ImageSlice<I>
+where
+	I : ImageAccess + ImageViewAccess,
+{
+	fn parent(&self) -> &dyn ImageAccess { &self.image }
+	fn dimensions(&self) -> Dimensions { Dimensions::Dim2d { width: self.dimensions[0], height: self.dimensions[1] } }
+	fn inner(&self) -> &UnsafeImageView { &self.view }
+	fn descriptor_set_storage_image_layout(&self) -> ImageLayout { self.image.descriptor_set_storage_image_layout() }
+	fn descriptor_set_combined_image_sampler_layout(&self) -> ImageLayout { self.image.descriptor_set_combined_image_sampler_layout() }
+	fn descriptor_set_sampled_image_layout(&self) -> ImageLayout { self.image.descriptor_set_sampled_image_layout() }
+	fn descriptor_set_input_attachment_layout(&self) -> ImageLayout { self.image.descriptor_set_input_attachment_layout() }
+	fn identity_swizzle(&self) -> bool { true }
+}
+
+// TODO/vulkano: reading back a region of a depth image (for CPU-side picking), vulkano 0.18 `copy_image_to_buffer` only supports color aspects.
+// The copied data should be converted using `format::unpack_depth()` once it is possible.
+
+// Upload a single-level image on the transfer queue, blocking until it is finished.
+// The image is shared between all queue families of the device, so it can be used on the graphics queue without an ownership transfer.
+fn upload_image<P, I, F>(device: &Device, data_iterator: I, dimensions: Dimensions, format: F)
+-> Result<Arc<ImmutableImage<F>>, ImageCreationError>
+where
+	P : Send + Sync + Clone + 'static,
+	F : FormatDesc + AcceptsPixels<P> + Send + Sync + 'static,
+	I : ExactSizeIterator<Item = P>,
+	Format: AcceptsPixels<P>,
+{
+	let source = CpuAccessibleBuffer::from_iter(device.logical_device(), BufferUsage::transfer_source(), false, data_iterator)?;
+	let usage = ImageUsage { transfer_destination: true, sampled: true, .. ImageUsage::none() };
+	let (image, initialization) = ImmutableImage::uninitialized(
+		device.logical_device(),
+		dimensions,
+		format,
+		MipmapsCount::One,
+		usage,
+		ImageLayout::ShaderReadOnlyOptimal,
+		device.sharing_families(),
+	)?;
+	device.upload_blocking(|commands| commands.copy_buffer_to_image_dimensions(
+		source,
+		initialization,
+		[0, 0, 0],
+		dimensions.width_height_depth(),
+		0,
+		dimensions.array_layers_with_cube(),
+		0,
+	).unwrap());
+
+	Ok(image)
+}
+
+// Get the dimensions of given mip level and array layer of an image, validating that they exist.
+fn slice_dimensions(dimensions: [u32; 2], levels: u32, layers: u32, level: u32, layer: u32) -> Result<[u32; 2], ImageSliceError> {
+	if level >= levels { return Err(ImageSliceError::MissingMipLevel { level, levels }); }
+	if layer >= layers { return Err(ImageSliceError::MissingArrayLayer { layer, layers }); }
+	Ok([(dimensions[0] >> level).max(1), (dimensions[1] >> level).max(1)])
+}
+
+// Get the bottom right corner of the first mip level of an image.
+fn image_corner<I: ImageAccess>(image: &I) -> [i32; 3] {
+	let dimensions = image.dimensions();
+	[dimensions.width() as i32, dimensions.height() as i32, dimensions.depth() as i32]
+}
+
+impl From<OomError> for ImageSliceError {
+	fn from(err: OomError) -> Self { Self::OutOfMemory(err) }
+}
+impl From<ImageCreationError> for CompressedImageError {
+	fn from(err: ImageCreationError) -> Self { Self::Creation(err) }
+}
+impl From<DeviceMemoryAllocError> for SampledImageError {
+	fn from(err: DeviceMemoryAllocError) -> Self { Self::Allocation(err) }
+}
+impl From<ImageCreationError> for SampledImageError {
+	fn from(err: ImageCreationError) -> Self { Self::Creation(err) }
+}
+
+impl std::fmt::Display for CompressedImageError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::UnsupportedFormat(format) => write!(fmt, "the format {:?} is not a supported block-compressed format", format),
+			Self::SizeMismatch { expected, actual } => write!(fmt, "{} bytes of data were provided, but the image requires {}", actual, expected),
+			Self::Creation(err) => write!(fmt, "failed to create the image: {}", err),
+		}
+	}
+}
+impl std::error::Error for CompressedImageError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Creation(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl std::fmt::Display for ImageSliceError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::MissingMipLevel { level, levels } => write!(fmt, "the mip level {} was requested, but the image has {}", level, levels),
+			Self::MissingArrayLayer { layer, layers } => write!(fmt, "the array layer {} was requested, but the image has {}", layer, layers),
+			Self::OutOfMemory(err) => write!(fmt, "failed to create the view: {}", err),
+		}
+	}
+}
+impl std::error::Error for ImageSliceError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::OutOfMemory(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl std::fmt::Display for SampledImageError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::UnsupportedFormat(format) => write!(fmt, "the format {:?} can not be used for sampled images", format),
+			Self::InvalidMipLevels(levels) => write!(fmt, "{} is not a valid number of mip levels for the image", levels),
+			Self::InvalidArrayLayers => write!(fmt, "the image requires at least 1 array layer"),
+			Self::SizeMismatch { expected, actual } => write!(fmt, "{} bytes of data were provided, but the image requires {}", actual, expected),
+			Self::Allocation(err) => write!(fmt, "failed to allocate the upload buffer: {}", err),
+			Self::Creation(err) => write!(fmt, "failed to create the image: {}", err),
+		}
+	}
+}
+impl std::error::Error for SampledImageError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Allocation(err) => Some(err),
+			Self::Creation(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_image_descriptor() {
+		let descriptor = ImageDescriptor::new([16, 4], Format::R8G8B8A8Unorm).array_layers(3);
+		assert_eq!(descriptor.max_mip_levels(), 5);
+		assert_eq!(descriptor.level_dimensions(3), [2, 1]);
+		assert_eq!(descriptor.data_size(), Some(16 * 4 * 3 * 4));
+		assert_eq!(descriptor.mip_levels(2).data_size(), Some((16 * 4 + 8 * 2) * 3 * 4));
+		assert_eq!(ImageDescriptor::new([4, 4], Format::BC1_RGBUnormBlock).data_size(), None);
+	}
+
+	#[test]
+	fn test_slice_dimensions() {
+		assert_eq!(slice_dimensions([16, 4], 5, 6, 3, 5), Ok([2, 1]));
+		assert_eq!(slice_dimensions([16, 4], 5, 6, 5, 0), Err(ImageSliceError::MissingMipLevel { level: 5, levels: 5 }));
+		assert_eq!(slice_dimensions([16, 4], 5, 6, 0, 6), Err(ImageSliceError::MissingArrayLayer { layer: 6, layers: 6 }));
+	}
+}
@@ -0,0 +1,83 @@
+//! Mesh bundles the vertex and index buffers of static geometry.
+//!
+//! A [`Mesh`](struct.Mesh.html) remembers the type of its indices, so drawing it is a single call:
+//! ```ignore
+//! let mesh = Mesh::new(&device, (vertices, indices))?;
+//! // In the main loop:
+//! let pass_in_frame = mesh.draw(pass_in_frame, descriptor_sets, push_constants);
+//! ```
+//!
+//! The [primitives](../geometry/index.html) can be converted into meshes using `Mesh::from()`.
+
+use super::buffer::{ImmutableBuffer, create_immutable_index_buffer, create_immutable_vertex_buffer};
+use super::device::Device;
+use super::frame::PassInFrame;
+use super::geometry::{GeometryBuffers, Vertex};
+
+use std::sync::Arc;
+
+use vulkano::descriptor::descriptor_set::DescriptorSetsCollection;
+use vulkano::memory::DeviceMemoryAllocError;
+use vulkano::pipeline::GraphicsPipelineAbstract;
+use vulkano::pipeline::input_assembly::Index;
+use vulkano::pipeline::vertex::VertexSource;
+
+/// Device-local vertex and index buffers of static geometry.
+///
+/// The indices are either `u16` or `u32`.
+pub struct Mesh<V, I = u32> {
+	vertices: Arc<ImmutableBuffer<[V]>>,
+	indices: Arc<ImmutableBuffer<[I]>>,
+}
+
+impl<V, I> Mesh<V, I>
+where
+	V : Send + Sync + 'static,
+	I : Index + Send + Sync + 'static,
+{
+	/// Upload vertices and indices into new device-local buffers.
+	///
+	/// # Panic.
+	///
+	/// - Panics if fails to submit the copy command buffers.
+	pub fn new(device: &Device, (vertices, indices): (Vec<V>, Vec<I>)) -> Result<Self, DeviceMemoryAllocError> {
+		let vertices = create_immutable_vertex_buffer(device, vertices.into_iter())?;
+		let indices = create_immutable_index_buffer(device, indices.into_iter())?;
+		Ok(Self { vertices, indices })
+	}
+
+	/// Bundle already existing buffers.
+	#[inline]
+	pub fn from_buffers(vertices: Arc<ImmutableBuffer<[V]>>, indices: Arc<ImmutableBuffer<[I]>>) -> Self { Self { vertices, indices } }
+
+	/// Get the vertex buffer of the mesh.
+	#[inline]
+	pub fn vertex_buffer(&self) -> &Arc<ImmutableBuffer<[V]>> { &self.vertices }
+	/// Get the index buffer of the mesh.
+	#[inline]
+	pub fn index_buffer(&self) -> &Arc<ImmutableBuffer<[I]>> { &self.indices }
+	/// Get the number of indices, which is the number of drawn vertices.
+	#[inline]
+	pub fn index_count(&self) -> usize { self.indices.len() }
+
+	/// Draw the mesh using the pass, see [`PassInFrame::draw_indexed()`](../frame/struct.PassInFrame.html#method.draw_indexed).
+	///
+	/// # Panic.
+	///
+	/// - Panics if fails to record the draw command.
+	pub fn draw<'a, P, DSC, PC>(&self, pass_in_frame: PassInFrame<'a, P>, descriptor_sets: DSC, push_constants: PC) -> PassInFrame<'a, P>
+	where
+		P : GraphicsPipelineAbstract + VertexSource<Arc<ImmutableBuffer<[V]>>> + Send + Sync + ?Sized + 'static,
+		DSC : DescriptorSetsCollection,
+	{
+		pass_in_frame.draw_indexed(self.vertices.clone(), self.indices.clone(), descriptor_sets, push_constants)
+	}
+}
+
+impl<V, I> Clone for Mesh<V, I> {
+	fn clone(&self) -> Self { Self { vertices: self.vertices.clone(), indices: self.indices.clone() } }
+}
+
+impl From<GeometryBuffers> for Mesh<Vertex> {
+	fn from((vertices, indices): GeometryBuffers) -> Self { Self { vertices, indices } }
+}
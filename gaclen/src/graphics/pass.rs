@@ -1,8 +1,19 @@
 //! Infrastructure containing device configuration used for computations.
+//! 
+//! A [`GraphicalPass`](struct.GraphicalPass.html) is used for drawing and a [`ComputePass`](struct.ComputePass.html) for general-purpose computations (dispatches).
 
 mod graphical_pass;
+mod compute_pass;
 mod builder;
+mod clear_values;
+mod descriptor_cache;
+mod framebuffer;
 
 pub use graphical_pass::*;
-pub use builder::{GraphicalPassBuilder, PrimitiveTopology, StoreOp, LoadOp};
+pub use compute_pass::ComputePass;
+pub use builder::{BufferlessPipeline, GraphicalPassBuilder, LogicOp, PrimitiveTopology, StoreOp, LoadOp};
+pub use framebuffer::{FramebufferBuilder, FramebufferBuildError};
+pub use clear_values::{AttachmentRole, ClearValuesBuilder, ClearValuesError};
+pub use descriptor_cache::DescriptorCache;
 pub use vulkano::descriptor::descriptor_set::{FixedSizeDescriptorSet, FixedSizeDescriptorSetsPool};
+pub use vulkano::pipeline::ComputePipelineCreationError;
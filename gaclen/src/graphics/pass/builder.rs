@@ -1,47 +1,77 @@
 use vulkano::pipeline::GraphicsPipelineAbstract;
 use crate::graphics;
 use graphics::device::Device;
+use graphics::format::{block_size, has_depth, has_stencil, supports_color_attachment};
 use graphics::swapchain::Swapchain;
 use graphics::pass::graphical_pass;
-use graphical_pass::{GraphicalPass, GraphicalRenderPassDescription};
+use graphical_pass::{GraphicalPass, GraphicalRenderPassDescription, PipelineState};
 
-use vulkano::format::{Format, PossibleDepthFormatDesc};
+use vulkano::descriptor::PipelineLayoutAbstract;
+use vulkano::format::Format;
 use vulkano::pipeline::{GraphicsPipeline, GraphicsPipelineCreationError};
+use vulkano::pipeline::blend::AttachmentBlend;
 use vulkano::pipeline::depth_stencil::{Compare, DepthStencil};
 use vulkano::pipeline::shader::{SpecializationConstants, GraphicsEntryPointAbstract};
 use vulkano::pipeline::raster::{CullMode, FrontFace, PolygonMode, Rasterization};
-use vulkano::pipeline::vertex::{SingleBufferDefinition, VertexDefinition};
-use vulkano::framebuffer::{AttachmentDescription, RenderPassDesc, RenderPassCreationError, Subpass};
+use vulkano::pipeline::vertex::{BufferlessDefinition, SingleBufferDefinition, TwoBuffersDefinition, VertexDefinition};
+use vulkano::framebuffer::{AttachmentDescription, RenderPass, RenderPassAbstract, RenderPassDesc, RenderPassCreationError, Subpass};
 use vulkano::image::ImageLayout;
 
 use std::sync::Arc;
 
+pub use vulkano::pipeline::blend::LogicOp;
 pub use vulkano::pipeline::input_assembly::PrimitiveTopology;
 pub use vulkano::framebuffer::{StoreOp, LoadOp};
 
+/// Pipeline of a pass without vertex input, built by [`GraphicalPassBuilder::build_bufferless()`](struct.GraphicalPassBuilder.html#method.build_bufferless).
+pub type BufferlessPipeline = TypedPipeline<BufferlessDefinition>;
+
+// Pipeline with a known vertex input type.
+type TypedPipeline<VI> = GraphicsPipeline<VI, Box<dyn PipelineLayoutAbstract + Send + Sync>, Arc<dyn RenderPassAbstract + Send + Sync>>;
+
 /// A structure for initializing [`GraphicalPasses`](struct.GraphicalPass.html).
 pub struct GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 	vertex_input: VI,
 	vertex_shader: (VS, VSS),
 	primitive_topology: PrimitiveTopology,
+	primitive_restart: bool,
 	rasterization: Rasterization,
 	fragment_shader: (FS, FSS),
 	depth_stencil: DepthStencil,
+	blend: AttachmentBlend,
+	logic_op: Option<LogicOp>,
+	sample_shading: Option<f32>,
+	alpha_to_coverage: bool,
+	viewport_count: u32,
 
 	samples: u32,
 	attachments: Vec<AttachmentDescription>,
 	depth_attachment: Option<usize>,
+	resolve_attachments: Vec<usize>,
 }
 
 /// Error during `GraphicalPassBuilder` setup.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AttachmentError {
 	/// The format supplied cannot be used for requested purposes.
-	InvalidFormat,
+	/// 
+	/// Contains the offending format.
+	InvalidFormat(Format),
 	/// A depth attachment already exists while trying to add one.
 	/// 
 	/// Contains the index of existing attachment.
 	DepthAttachmentAlreadyExists(usize),
+	/// A swapchain depth attachment was requested, but the swapchain has no depth images.
+	NoSwapchainDepth,
+	/// A resolve attachment was added without a multisampled color attachment to resolve.
+	NoAttachmentToResolve,
+	/// The format of a resolve attachment does not match the one of the color attachment it resolves.
+	ResolveFormatMismatch {
+		/// Format of the resolved color attachment.
+		expected: Format,
+		/// Format of the resolve attachment.
+		actual: Format,
+	},
 }
 
 /// Error during GraphicalPassBuilder::build.
@@ -53,6 +83,21 @@ pub enum BuildError {
 	GraphicsPipelineCreation(GraphicsPipelineCreationError),
 	/// No attachments were added to the pass, therefore no invocation is possible!
 	NoAttachments,
+	/// An attachment of the pass is not supported by the device.
+	InvalidAttachment {
+		/// Index of the attachment.
+		index: usize,
+		/// Reason the attachment is not supported.
+		error: AttachmentError,
+	},
+	/// A [logic operation](struct.GraphicalPassBuilder.html#method.logic_op) was set, but the `logic_op` feature is not enabled for the device.
+	LogicOpNotEnabled,
+	/// [Sample shading](struct.GraphicalPassBuilder.html#method.sample_shading) was enabled, but the `sample_rate_shading` feature is not enabled for the device.
+	SampleShadingNotEnabled,
+	/// [Depth clamping](struct.GraphicalPassBuilder.html#method.clamp_depth) was enabled, but the `depth_clamp` feature is not enabled for the device.
+	DepthClampNotEnabled,
+	/// [Alpha to coverage](struct.GraphicalPassBuilder.html#method.alpha_to_coverage) was enabled, but none of the attachments is [multisampled](struct.GraphicalPassBuilder.html#method.multisampled).
+	AlphaToCoverageWithoutMultisampling,
 }
 
 impl GraphicalPassBuilder<(), (), (), (), ()> {
@@ -61,13 +106,20 @@ impl GraphicalPassBuilder<(), (), (), (), ()> {
 			vertex_input: (),
 			vertex_shader: ((), ()),
 			primitive_topology: PrimitiveTopology::TriangleList,
+			primitive_restart: false,
 			rasterization: Rasterization::default(),
 			fragment_shader: ((), ()),
 			depth_stencil: DepthStencil::default(),
+			blend: AttachmentBlend::pass_through(),
+			logic_op: None,
+			sample_shading: None,
+			alpha_to_coverage: false,
+			viewport_count: 1,
 
 			samples: 1,
 			attachments: Vec::default(),
 			depth_attachment: None,
+			resolve_attachments: Vec::default(),
 		}
 	}
 }
@@ -79,19 +131,36 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 			vertex_input: vertex_input,
 			vertex_shader: self.vertex_shader,
 			primitive_topology: self.primitive_topology,
+			primitive_restart: self.primitive_restart,
 			rasterization: self.rasterization,
 			fragment_shader: self.fragment_shader,
 			depth_stencil: self.depth_stencil,
+			blend: self.blend,
+			logic_op: self.logic_op,
+			sample_shading: self.sample_shading,
+			alpha_to_coverage: self.alpha_to_coverage,
+			viewport_count: self.viewport_count,
 
 			samples: self.samples,
 			attachments: self.attachments,
 			depth_attachment: self.depth_attachment,
+			resolve_attachments: self.resolve_attachments,
 		}
 	}
 
 	/// Use a single buffer of provided vertex type as input.
 	pub fn single_buffer_input<V>(self) -> GraphicalPassBuilder<SingleBufferDefinition<V>, VS, VSS, FS, FSS> { self.vertex_input(SingleBufferDefinition::<V>::new()) }
 
+	/// Use two buffers as input: binding 0 of provided per-vertex type and binding 1 of provided per-instance type.
+	/// 
+	/// Allows drawing multiple instances of the same geometry using [`PassInFrame::draw_instanced()`](../frame/struct.PassInFrame.html#method.draw_instanced).
+	pub fn two_buffers_input<V, I>(self) -> GraphicalPassBuilder<TwoBuffersDefinition<V, I>, VS, VSS, FS, FSS> { self.vertex_input(TwoBuffersDefinition::<V, I>::new()) }
+
+	/// Use no vertex buffers as input, the vertex shader generates vertices from their index instead (such as a fullscreen triangle from `gl_VertexIndex`).
+	/// 
+	/// The pass should be built with [`build_bufferless()`](#method.build_bufferless) and drawn with [`PassInFrame::draw_unbound()`](../frame/struct.PassInFrame.html#method.draw_unbound).
+	pub fn bufferless_input(self) -> GraphicalPassBuilder<BufferlessDefinition, VS, VSS, FS, FSS> { self.vertex_input(BufferlessDefinition) }
+
 	/// Use given [`PrimitiveTopology`](enum.PrimitiveTopology.html).
 	/// 
 	/// Default is [`PrimitiveTopology::TriangleList`](enum.PrimitiveTopology.html#variant.TriangleList).
@@ -121,10 +190,20 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 	/// Use [`PrimitiveTopology::PatchList`](enum.PrimitiveTopology.html#variant.PatchList).
 	pub fn patch_list(self, vertices_per_patch: u32) -> Self { self.primitive_topology(PrimitiveTopology::PatchList{ vertices_per_patch }) }
 
+	/// Set whether a special index value restarts the primitive during indexed draws.
+	/// 
+	/// The restart index is the maximum value of the index type (`0xFFFF` for `u16` and `0xFFFFFFFF` for `u32` indices).
+	/// Allows drawing multiple strips or fans with a single draw call, can not be used with list topologies.
+	/// Default is `false`.
+	pub fn primitive_restart(mut self, enable: bool) -> Self { self.primitive_restart = enable; self }
+
 	/// Set whether to clamp depth values of vertices.
 	/// 
 	/// If true vertices with depth outside [0 : 1] range will be clamp to those values.
 	/// If false those vertices will be dropped.
+	/// 
+	/// Clamping is useful for drawing shadow maps, as it keeps shadow casters in front of the near plane.
+	/// Requires the `depth_clamp` device feature, building the pass fails otherwise.
 	pub fn clamp_depth(mut self, clamp: bool) -> Self { self.rasterization.depth_clamp = clamp; self }
 
 	/// Use provided [`PolygonMode`](enum.PolygonMode.html) for rasterizer (disassemble input primitives into provided types).
@@ -161,6 +240,8 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 	// /// Set the width of the lines drawn as dynamic, requiring their specification during draw call.
 	// pub fn line_width_dynamic(mut self) -> Self { self.rasterization.line_width = None; self }
 
+	// TODO/vulkano: static and dynamic depth bias (`depth_bias()`, `depth_bias_dynamic()`) for per-cascade shadow map bias, vulkano 0.18 `GraphicsPipelineBuilder` does not allow setting the depth bias state and `DynamicState` has no depth bias values.
+
 	/// Set whether to write to the depth buffer.
 	/// 
 	/// Default is `false`.
@@ -197,6 +278,62 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 	/// Shortcut for `depth_write(true)` and `depth_test_greater()`.
 	pub fn inverse_depth_test(self) -> Self { self.depth_write(true).depth_test_greater() }
 
+	/// Set which components of the color are written to the color attachments.
+	/// 
+	/// Default is writing all components.
+	pub fn color_write_mask(mut self, red: bool, green: bool, blue: bool, alpha: bool) -> Self {
+		self.blend.mask_red = red;
+		self.blend.mask_green = green;
+		self.blend.mask_blue = blue;
+		self.blend.mask_alpha = alpha;
+		self
+	}
+
+	/// Combine the written colors with existing ones using a bitwise logic operation.
+	/// 
+	/// Requires the `logic_op` device feature, building the pass fails otherwise.
+	/// Logic operations only apply to integer and normalized color formats.
+	pub fn logic_op(mut self, operation: LogicOp) -> Self { self.logic_op = Some(operation); self }
+
+	/// Shade multisampled attachments per sample instead of per pixel, reducing aliasing within triangles (such as of specular highlights or alpha-tested edges) at a performance cost.
+	/// 
+	/// At least `min_fraction` of samples of each pixel are shaded separately, `1.0` shades every sample.
+	/// Only has effect on [multisampled](#method.multisampled) attachments.
+	/// Requires the `sample_rate_shading` device feature, building the pass fails otherwise.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if `min_fraction` is not in `[0.0, 1.0]` range.
+	pub fn sample_shading(mut self, min_fraction: f32) -> Self {
+		assert!((0.0..=1.0).contains(&min_fraction), "The fraction of shaded samples should be in [0.0, 1.0] range.");
+		self.sample_shading = Some(min_fraction);
+		self
+	}
+
+	/// Shade multisampled attachments once per pixel (default).
+	pub fn sample_shading_disabled(mut self) -> Self { self.sample_shading = None; self }
+
+	/// Derive the coverage of each sample from the alpha of the first color output, smoothing the edges of alpha-tested geometry (such as foliage).
+	/// 
+	/// Only has effect on [multisampled](#method.multisampled) attachments, building the pass fails if none of the attachments is multisampled.
+	/// Default is `false`.
+	pub fn alpha_to_coverage(mut self, enable: bool) -> Self { self.alpha_to_coverage = enable; self }
+
+	/// Set the number of viewports the pass draws to, used for layered rendering.
+	/// 
+	/// The same number of viewports should be provided with [`Frame::begin_pass_with_viewports()`](../frame/struct.Frame.html#method.begin_pass_with_viewports).
+	/// More than 1 viewport requires the `multi_viewport` device feature.
+	/// Default is `1`.
+	/// 
+	/// # Panic.
+	/// 
+	/// - Panics if `count` is `0`.
+	pub fn viewport_count(mut self, count: u32) -> Self {
+		assert!(count >= 1, "A pass requires at least 1 viewport.");
+		self.viewport_count = count;
+		self
+	}
+
 	/// Use given vertex shader with given specialization constants.
 	pub fn vertex_shader<S, SC>(self, shader: S, specialization: SC)
 	-> GraphicalPassBuilder<VI, S, SC, FS, FSS> 
@@ -208,13 +345,20 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 			vertex_input: self.vertex_input,
 			vertex_shader: (shader, specialization),
 			primitive_topology: self.primitive_topology,
+			primitive_restart: self.primitive_restart,
 			rasterization: self.rasterization,
 			fragment_shader: self.fragment_shader,
 			depth_stencil: self.depth_stencil,
+			blend: self.blend,
+			logic_op: self.logic_op,
+			sample_shading: self.sample_shading,
+			alpha_to_coverage: self.alpha_to_coverage,
+			viewport_count: self.viewport_count,
 
 			samples: self.samples,
 			attachments: self.attachments,
 			depth_attachment: self.depth_attachment,
+			resolve_attachments: self.resolve_attachments,
 		}
 	}
 
@@ -229,17 +373,33 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 			vertex_input: self.vertex_input,
 			vertex_shader: self.vertex_shader,
 			primitive_topology: self.primitive_topology,
+			primitive_restart: self.primitive_restart,
 			rasterization: self.rasterization,
 			fragment_shader: (shader, specialization),
 			depth_stencil: self.depth_stencil,
+			blend: self.blend,
+			logic_op: self.logic_op,
+			sample_shading: self.sample_shading,
+			alpha_to_coverage: self.alpha_to_coverage,
+			viewport_count: self.viewport_count,
 
 			samples: self.samples,
 			attachments: self.attachments,
 			depth_attachment: self.depth_attachment,
+			resolve_attachments: self.resolve_attachments,
 		}
 	}
 
+	/// Set the number of samples per pixel of the attachments added after this call.
+	/// 
+	/// Multisampled color attachments should be resolved to single-sampled ones using [`add_resolve_attachment()`](#method.add_resolve_attachment) before being presented.
+	/// Default is `1`.
+	pub fn multisampled(mut self, samples: u32) -> Self { self.samples = samples; self }
+
 	/// Append an image attachment (resource that is drawn to) to this pass.
+	/// 
+	/// The format is validated to be renderable by the device during [`build()`](#method.build).
+	/// See [format](../format/index.html#data-formats) for formats that are supported everywhere.
 	pub fn add_image_attachment(mut self, format: Format, load: LoadOp, store: StoreOp) -> Self {
 		self.attachments.push(AttachmentDescription{
 			format,
@@ -268,11 +428,70 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 		self.add_image_attachment_swapchain(swapchain, LoadOp::Clear)
 	}
 
+	/// Append a color attachment of an offscreen target (such as a [`RenderTarget`](../render_target/struct.RenderTarget.html)) to this pass.
+	/// 
+	/// The attachment is cleared when the pass begins and stored afterwards, for example to be sampled by a later pass.
+	/// Fails with [`AttachmentError::InvalidFormat`](enum.AttachmentError.html#variant.InvalidFormat) for depth and compressed formats.
+	/// Whether the device can render to the format is validated during [`build()`](#method.build).
+	pub fn add_color_target(self, format: Format) -> Result<Self, AttachmentError> {
+		if has_depth(format) || block_size(format).is_some() {
+			return Err(AttachmentError::InvalidFormat(format));
+		}
+		Ok(self.add_image_attachment(format, LoadOp::Clear, StoreOp::Store))
+	}
+
+	/// Append a single-sampled attachment the next unresolved multisampled color attachment is resolved into.
+	/// 
+	/// Fails if there is no multisampled color attachment left to resolve or if the format does not match the one of resolved attachment.
+	pub fn add_resolve_attachment(mut self, format: Format, store: StoreOp) -> Result<Self, AttachmentError> {
+		let resolved = {
+			let description = GraphicalRenderPassDescription {
+				attachments: self.attachments.clone(),
+				depth_attachment: self.depth_attachment,
+				resolve_attachments: self.resolve_attachments.clone(),
+			};
+			description.color_attachment_indices().nth(self.resolve_attachments.len())
+		};
+		let resolved = match resolved {
+			Some(index) if self.attachments[index].samples > 1 => &self.attachments[index],
+			_ => return Err(AttachmentError::NoAttachmentToResolve),
+		};
+		if resolved.format != format {
+			return Err(AttachmentError::ResolveFormatMismatch { expected: resolved.format, actual: format });
+		}
+
+		self.resolve_attachments.push(self.attachments.len());
+		self.attachments.push(AttachmentDescription{
+			format,
+			samples: 1,
+			load: LoadOp::DontCare,
+			store,
+			stencil_load: LoadOp::DontCare,
+			stencil_store: StoreOp::DontCare,
+			initial_layout: ImageLayout::ColorAttachmentOptimal,
+			final_layout: ImageLayout::ColorAttachmentOptimal,
+		});
+		Ok(self)
+	}
+
+	/// Append a single-sampled attachment the next unresolved multisampled color attachment is resolved into.
+	/// 
+	/// In particular set up the pass to resolve into swapchain image (frame result) of a device.
+	pub fn add_resolve_attachment_swapchain(self, swapchain: &Swapchain) -> Result<Self, AttachmentError> {
+		self.add_resolve_attachment(swapchain.swapchain.format(), StoreOp::Store)
+	}
+
 	/// Append a depth-buffer attachment (resource that is drawn to) to this pass.
 	/// 
 	/// May fail if a depth attachment was already appended (currently only 1 is supported at a time).
+	/// The stencil component (if the format has one) uses the same operations as depth.
 	pub fn add_depth_attachment(mut self, format: Format, load: LoadOp, store: StoreOp) -> Result<Self, AttachmentError> {
-		if format.is_depth() {
+		if has_depth(format) {
+			// Depth-only formats have no stencil to load or store.
+			let (stencil_load, stencil_store) = match has_stencil(format) {
+				true => (load, store),
+				false => (LoadOp::DontCare, StoreOp::DontCare),
+			};
 			match self.depth_attachment {
 				Some(index) => Err(AttachmentError::DepthAttachmentAlreadyExists(index)),
 				None => {
@@ -282,8 +501,8 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 						samples: self.samples,
 						load: load,
 						store: store,
-						stencil_load: load,
-						stencil_store: store,
+						stencil_load,
+						stencil_store,
 						initial_layout: ImageLayout::DepthStencilAttachmentOptimal,
 						final_layout: ImageLayout::DepthStencilAttachmentOptimal,
 					});
@@ -291,7 +510,7 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 				}
 			}
 		} else {
-			Err(AttachmentError::InvalidFormat)
+			Err(AttachmentError::InvalidFormat(format))
 		}
 	}
 
@@ -299,8 +518,12 @@ impl<VI, VS, VSS, FS, FSS> GraphicalPassBuilder<VI, VS, VSS, FS, FSS> {
 	/// 
 	/// May fail if a depth attachment was already appended (currently only 1 is supported at a time).
 	/// In particular set up the pass to use swapchain depth of a device.
+	/// Fails if the swapchain was created without depth.
 	pub fn add_depth_attachment_swapchain(self, swapchain: &Swapchain, load: LoadOp, store: StoreOp) -> Result<Self, AttachmentError> {
-		self.add_depth_attachment(swapchain.depth_format, load, store)
+		match swapchain.depth_format {
+			Some(format) => self.add_depth_attachment(format, load, store),
+			None => Err(AttachmentError::NoSwapchainDepth),
+		}
 	}
 
 	/// Append a depth-buffer attachment (resource that is drawn to) to this pass.
@@ -331,63 +554,190 @@ where
 	pub fn build(self, device: &Device)
 	-> Result<GraphicalPass<dyn GraphicsPipelineAbstract + Send + Sync + 'static>, BuildError>
 	{
+		let pass = self.build_typed(device)?;
+		Ok(GraphicalPass { pipeline: pass.pipeline, render_pass: pass.render_pass, state: pass.state, description: pass.description })
+	}
+
+	fn build_typed(self, device: &Device) -> Result<GraphicalPass<TypedPipeline<VI>>, BuildError> {
 		if self.attachments.is_empty() {
 			return Err(BuildError::NoAttachments)
 		};
+		if self.logic_op.is_some() && !device.enabled_features().logic_op {
+			return Err(BuildError::LogicOpNotEnabled)
+		};
+		if self.sample_shading.is_some() && !device.enabled_features().sample_rate_shading {
+			return Err(BuildError::SampleShadingNotEnabled)
+		};
+		if self.rasterization.depth_clamp && !device.enabled_features().depth_clamp {
+			return Err(BuildError::DepthClampNotEnabled)
+		};
+		if self.alpha_to_coverage && self.attachments.iter().all(|attachment| attachment.samples == 1) {
+			return Err(BuildError::AlphaToCoverageWithoutMultisampling)
+		};
 
-		let render_pass = {
-			let description = GraphicalRenderPassDescription {
-				attachments: self.attachments,
-				depth_attachment: self.depth_attachment,
-			};
-			Arc::new(description.build_render_pass(device.device.clone())?)
+		let description = GraphicalRenderPassDescription {
+			attachments: self.attachments,
+			depth_attachment: self.depth_attachment,
+			resolve_attachments: self.resolve_attachments,
 		};
 
-		let pipeline = {
-			let builder = GraphicsPipeline::start()
-			.vertex_input(self.vertex_input)
-			.vertex_shader(self.vertex_shader.0, self.vertex_shader.1)
-			.primitive_topology(self.primitive_topology)
-			.viewports_dynamic_scissors_irrelevant(1)
-			.fragment_shader(self.fragment_shader.0, self.fragment_shader.1)
-			.depth_stencil(self.depth_stencil)
-			.render_pass(Subpass::from(render_pass, 0).unwrap())
-			.depth_clamp(self.rasterization.depth_clamp)
-			;
-
-			let builder = match self.rasterization.polygon_mode {
-				PolygonMode::Point => builder.polygon_mode_point(),
-				PolygonMode::Line => builder.polygon_mode_line(),
-				PolygonMode::Fill => builder.polygon_mode_fill(),
-			};
+		for (index, attachment) in description.attachments.iter().enumerate() {
+			if description.depth_attachment != Some(index) && !supports_color_attachment(device, attachment.format) {
+				return Err(BuildError::InvalidAttachment { index, error: AttachmentError::InvalidFormat(attachment.format) })
+			}
+		};
 
-			let builder = match self.rasterization.cull_mode {
-				CullMode::None => builder.cull_mode_disabled(),
-				CullMode::Front => builder.cull_mode_front(),
-				CullMode::Back => builder.cull_mode_back(),
-				CullMode::FrontAndBack => builder.cull_mode_front_and_back(),
-			};
+		let render_pass = {
+			Arc::new(description.clone().build_render_pass(device.device.clone())?)
+		};
 
-			let builder = match self.rasterization.front_face {
-				FrontFace::Clockwise => builder.front_face_clockwise(),
-				FrontFace::CounterClockwise => builder.front_face_counter_clockwise(),
-			};
+		let state = PipelineState {
+			primitive_topology: self.primitive_topology,
+			primitive_restart: self.primitive_restart,
+			rasterization: self.rasterization,
+			depth_stencil: self.depth_stencil,
+			blend: self.blend,
+			logic_op: self.logic_op,
+			sample_shading: self.sample_shading,
+			alpha_to_coverage: self.alpha_to_coverage,
+			viewport_count: self.viewport_count,
+		};
 
-			let builder = match self.rasterization.line_width {
-				Some(width) => builder.line_width(width),
-				None => builder,
-			};
+		let pipeline = build_pipeline(device, self.vertex_input, self.vertex_shader, self.fragment_shader, &state, render_pass.clone())?;
 
-			Arc::new(builder.build(device.logical_device())?)
-		};
-		
-		Ok(GraphicalPass { pipeline, })
+		Ok(GraphicalPass { pipeline, render_pass, state, description, })
 	}
 }
 
+impl<VS, VSS, FS, FSS> GraphicalPassBuilder<BufferlessDefinition, VS, VSS, FS, FSS>
+where
+	VS : GraphicsEntryPointAbstract<SpecializationConstants=VSS>,
+	FS : GraphicsEntryPointAbstract<SpecializationConstants=FSS>,
+	VSS : SpecializationConstants,
+	FSS : SpecializationConstants,
+	VS::PipelineLayout : Send + Sync + Clone + 'static,
+	FS::PipelineLayout : Send + Sync + Clone + 'static,
+{
+	/// Build a pass without vertex input, see [`bufferless_input()`](#method.bufferless_input).
+	/// 
+	/// Same as [`build()`](#method.build), but keeps the type of the pipeline, which allows [drawing without vertex buffers](../frame/struct.PassInFrame.html#method.draw_unbound).
+	pub fn build_bufferless(self, device: &Device) -> Result<GraphicalPass<BufferlessPipeline>, BuildError> { self.build_typed(device) }
+}
+
+// Build a pipeline for the first subpass of given render pass.
+pub(super) fn build_pipeline<VI, VS, VSS, FS, FSS>(
+	device: &Device,
+	vertex_input: VI,
+	vertex_shader: (VS, VSS),
+	fragment_shader: (FS, FSS),
+	state: &PipelineState,
+	render_pass: Arc<RenderPass<GraphicalRenderPassDescription>>,
+) -> Result<Arc<TypedPipeline<VI>>, GraphicsPipelineCreationError>
+where
+	VS : GraphicsEntryPointAbstract<SpecializationConstants=VSS>,
+	FS : GraphicsEntryPointAbstract<SpecializationConstants=FSS>,
+	VSS : SpecializationConstants,
+	FSS : SpecializationConstants,
+	VS::PipelineLayout : Send + Sync + Clone + 'static,
+	FS::PipelineLayout : Send + Sync + Clone + 'static,
+	VI : VertexDefinition<VS::InputDefinition> + Send + Sync + 'static,
+{
+	let render_pass: Arc<dyn RenderPassAbstract + Send + Sync> = render_pass;
+	let builder = GraphicsPipeline::start()
+	.vertex_input(vertex_input)
+	.vertex_shader(vertex_shader.0, vertex_shader.1)
+	.primitive_topology(state.primitive_topology)
+	.primitive_restart(state.primitive_restart)
+	.viewports_dynamic_scissors_irrelevant(state.viewport_count)
+	.fragment_shader(fragment_shader.0, fragment_shader.1)
+	.depth_stencil(state.depth_stencil.clone())
+	.render_pass(Subpass::from(render_pass, 0).unwrap())
+	.depth_clamp(state.rasterization.depth_clamp)
+	.blend_collective(state.blend)
+	;
+
+	let builder = match state.logic_op {
+		Some(operation) => builder.blend_logic_op(operation),
+		None => builder.blend_logic_op_disabled(),
+	};
+
+	let builder = match state.sample_shading {
+		Some(min_fraction) => builder.sample_shading_enabled(min_fraction),
+		None => builder.sample_shading_disabled(),
+	};
+
+	let builder = match state.alpha_to_coverage {
+		true => builder.alpha_to_coverage_enabled(),
+		false => builder.alpha_to_coverage_disabled(),
+	};
+
+	let builder = match state.rasterization.polygon_mode {
+		PolygonMode::Point => builder.polygon_mode_point(),
+		PolygonMode::Line => builder.polygon_mode_line(),
+		PolygonMode::Fill => builder.polygon_mode_fill(),
+	};
+
+	let builder = match state.rasterization.cull_mode {
+		CullMode::None => builder.cull_mode_disabled(),
+		CullMode::Front => builder.cull_mode_front(),
+		CullMode::Back => builder.cull_mode_back(),
+		CullMode::FrontAndBack => builder.cull_mode_front_and_back(),
+	};
+
+	let builder = match state.rasterization.front_face {
+		FrontFace::Clockwise => builder.front_face_clockwise(),
+		FrontFace::CounterClockwise => builder.front_face_counter_clockwise(),
+	};
+
+	let builder = match state.rasterization.line_width {
+		Some(width) => builder.line_width(width),
+		None => builder,
+	};
+
+	Ok(Arc::new(builder.build(device.logical_device())?))
+}
+
 impl From<RenderPassCreationError> for BuildError {
 	fn from(err: RenderPassCreationError) -> Self { Self::RenderPassCreation(err) }
 }
 impl From<GraphicsPipelineCreationError> for BuildError {
 	fn from(err: GraphicsPipelineCreationError) -> Self { Self::GraphicsPipelineCreation(err) }
 }
+
+impl std::fmt::Display for AttachmentError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::InvalidFormat(format) => write!(fmt, "the format {:?} can not be used for the attachment", format),
+			Self::DepthAttachmentAlreadyExists(index) => write!(fmt, "a depth attachment already exists at index {}", index),
+			Self::NoSwapchainDepth => write!(fmt, "the swapchain has no depth images"),
+			Self::NoAttachmentToResolve => write!(fmt, "there is no multisampled color attachment to resolve"),
+			Self::ResolveFormatMismatch { expected, actual } => write!(fmt, "the resolve attachment format {:?} does not match the resolved format {:?}", actual, expected),
+		}
+	}
+}
+impl std::error::Error for AttachmentError {}
+
+impl std::fmt::Display for BuildError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::RenderPassCreation(err) => write!(fmt, "failed to create the render pass: {}", err),
+			Self::GraphicsPipelineCreation(err) => write!(fmt, "failed to create the graphics pipeline: {}", err),
+			Self::NoAttachments => write!(fmt, "the pass has no attachments"),
+			Self::InvalidAttachment { index, error } => write!(fmt, "the attachment at index {} is invalid: {}", index, error),
+			Self::LogicOpNotEnabled => write!(fmt, "a logic operation was set, but the logic_op feature is not enabled"),
+			Self::SampleShadingNotEnabled => write!(fmt, "sample shading was enabled, but the sample_rate_shading feature is not enabled"),
+			Self::DepthClampNotEnabled => write!(fmt, "depth clamping was enabled, but the depth_clamp feature is not enabled"),
+			Self::AlphaToCoverageWithoutMultisampling => write!(fmt, "alpha to coverage was enabled, but none of the attachments is multisampled"),
+		}
+	}
+}
+impl std::error::Error for BuildError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::RenderPassCreation(err) => Some(err),
+			Self::GraphicsPipelineCreation(err) => Some(err),
+			Self::InvalidAttachment { error, .. } => Some(error),
+			_ => None,
+		}
+	}
+}
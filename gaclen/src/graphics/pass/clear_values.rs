@@ -0,0 +1,203 @@
+use super::graphical_pass::GraphicalRenderPassDescription;
+
+use vulkano::format::ClearValue;
+use vulkano::framebuffer::LoadOp;
+
+/// A structure for building clear values for [`Frame::begin_pass()`](../frame/struct.Frame.html#method.begin_pass).
+///
+/// The values should be added in the same order as the attachments of the [`GraphicalPass`](struct.GraphicalPass.html) it was created by.
+/// Alternatively the values can be [set](#method.set) by the [role](enum.AttachmentRole.html) of the attachment in any order, attachments without a value are not cleared.
+/// The two ways should not be mixed.
+pub struct ClearValuesBuilder<'a> {
+	description: &'a GraphicalRenderPassDescription,
+	values: Vec<ClearValue>,
+	// values were set by attachment role, missing ones are not cleared
+	by_role: bool,
+	// first error encountered while setting values by role
+	error: Option<ClearValuesError>,
+}
+
+/// Role of an attachment in a [`GraphicalPass`](struct.GraphicalPass.html), used to refer to it regardless of the order attachments were added in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttachmentRole {
+	/// N-th color attachment (not counting depth and resolve ones), such as the one added by [`add_image_attachment_swapchain()`](struct.GraphicalPassBuilder.html#method.add_image_attachment_swapchain).
+	Color(usize),
+	/// The depth attachment.
+	Depth,
+	/// N-th resolve attachment.
+	Resolve(usize),
+}
+
+/// Error during building clear values for a [`GraphicalPass`](struct.GraphicalPass.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClearValuesError {
+	/// The number of provided values does not match the number of attachments of the pass.
+	CountMismatch {
+		/// Number of attachments of the pass.
+		expected: usize,
+		/// Number of provided values.
+		actual: usize,
+	},
+	/// A color value was provided for a depth attachment or vice versa.
+	///
+	/// Contains the index of the mismatched attachment.
+	KindMismatch(usize),
+	/// An attachment that is cleared when the pass begins has no clear value.
+	///
+	/// Contains the index of the attachment.
+	MissingValue(usize),
+	/// A clear value was provided for an attachment that is not cleared when the pass begins, `ClearValue::None` is expected instead.
+	///
+	/// Contains the index of the attachment.
+	UnexpectedValue(usize),
+	/// A value was set for an attachment role the pass does not have.
+	MissingAttachment(AttachmentRole),
+}
+
+impl<'a> ClearValuesBuilder<'a> {
+	pub(super) fn new(description: &'a GraphicalRenderPassDescription) -> Self {
+		Self { description, values: Vec::with_capacity(description.attachments.len()), by_role: false, error: None }
+	}
+
+	/// Append a clear value for the next attachment.
+	pub fn value(mut self, value: ClearValue) -> Self { self.values.push(value); self }
+	/// Append a floating-point color clear value for the next attachment.
+	pub fn color(self, color: [f32; 4]) -> Self { self.value(ClearValue::Float(color)) }
+	/// Append a depth clear value for the next attachment.
+	pub fn depth(self, depth: f32) -> Self { self.value(ClearValue::Depth(depth)) }
+	/// Append a depth-stencil clear value for the next attachment.
+	pub fn depth_stencil(self, depth: f32, stencil: u32) -> Self { self.value(ClearValue::DepthStencil((depth, stencil))) }
+	/// Append an empty clear value for the next attachment, used for attachments that are not cleared.
+	pub fn none(self) -> Self { self.value(ClearValue::None) }
+
+	/// Set the clear value of the attachment with given role.
+	/// 
+	/// Attachments without a set value are not cleared (use `ClearValue::None`).
+	/// Setting a value for a role the pass does not have results in [`ClearValuesError::MissingAttachment`](enum.ClearValuesError.html#variant.MissingAttachment) during [`build()`](#method.build).
+	pub fn set(mut self, role: AttachmentRole, value: ClearValue) -> Self {
+		self.by_role = true;
+		match attachment_index(self.description, role) {
+			Some(index) => {
+				if self.values.len() <= index { self.values.resize(index + 1, ClearValue::None); }
+				self.values[index] = value;
+			},
+			None => { self.error.get_or_insert(ClearValuesError::MissingAttachment(role)); },
+		};
+		self
+	}
+	/// Set a floating-point color clear value for the attachment with given role.
+	pub fn clear_color(self, role: AttachmentRole, color: [f32; 4]) -> Self { self.set(role, ClearValue::Float(color)) }
+	/// Set the depth clear value of the depth attachment.
+	pub fn clear_depth(self, depth: f32) -> Self { self.set(AttachmentRole::Depth, ClearValue::Depth(depth)) }
+	/// Set the depth-stencil clear value of the depth attachment.
+	pub fn clear_depth_stencil(self, depth: f32, stencil: u32) -> Self { self.set(AttachmentRole::Depth, ClearValue::DepthStencil((depth, stencil))) }
+
+	/// Validate the values against the attachments of the pass and build them.
+	///
+	/// Attachments that use `LoadOp::Clear` require a value, others require `ClearValue::None`.
+	pub fn build(mut self) -> Result<Vec<ClearValue>, ClearValuesError> {
+		if let Some(error) = self.error { return Err(error); }
+		if self.by_role && self.values.len() < self.description.attachments.len() {
+			self.values.resize(self.description.attachments.len(), ClearValue::None);
+		}
+		validate(self.description, &self.values)?;
+		Ok(self.values)
+	}
+}
+
+// Find the index of the attachment with given role.
+fn attachment_index(description: &GraphicalRenderPassDescription, role: AttachmentRole) -> Option<usize> {
+	match role {
+		AttachmentRole::Color(n) => description.color_attachment_indices().nth(n),
+		AttachmentRole::Depth => description.depth_attachment,
+		AttachmentRole::Resolve(n) => description.resolve_attachments.get(n).cloned(),
+	}
+}
+
+// Check that the clear values match the attachments of a render pass.
+pub(super) fn validate(description: &GraphicalRenderPassDescription, values: &[ClearValue]) -> Result<(), ClearValuesError> {
+	let expected = description.attachments.len();
+	if values.len() != expected {
+		return Err(ClearValuesError::CountMismatch { expected, actual: values.len() });
+	}
+
+	for (index, (value, attachment)) in values.iter().zip(description.attachments.iter()).enumerate() {
+		let is_depth_attachment = description.depth_attachment == Some(index);
+		let is_cleared = attachment.load == LoadOp::Clear || (is_depth_attachment && attachment.stencil_load == LoadOp::Clear);
+		let matches = match value {
+			ClearValue::None if is_cleared => return Err(ClearValuesError::MissingValue(index)),
+			ClearValue::None => true,
+			_ if !is_cleared => return Err(ClearValuesError::UnexpectedValue(index)),
+			ClearValue::Float(_) | ClearValue::Int(_) | ClearValue::Uint(_) => !is_depth_attachment,
+			ClearValue::Depth(_) | ClearValue::Stencil(_) | ClearValue::DepthStencil(_) => is_depth_attachment,
+		};
+		if !matches { return Err(ClearValuesError::KindMismatch(index)); }
+	};
+
+	Ok(())
+}
+
+impl std::fmt::Display for ClearValuesError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::CountMismatch { expected, actual } => write!(fmt, "{} clear values were provided, but the pass has {} attachments", actual, expected),
+			Self::KindMismatch(index) => write!(fmt, "the clear value at index {} does not match the kind of the attachment", index),
+			Self::MissingValue(index) => write!(fmt, "the attachment at index {} is cleared, but has no clear value", index),
+			Self::UnexpectedValue(index) => write!(fmt, "the attachment at index {} is not cleared, but has a clear value", index),
+			Self::MissingAttachment(role) => write!(fmt, "a clear value was set for {:?} attachment, but the pass has none", role),
+		}
+	}
+}
+impl std::error::Error for ClearValuesError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vulkano::format::Format;
+	use vulkano::framebuffer::{AttachmentDescription, StoreOp};
+	use vulkano::image::ImageLayout;
+
+	fn attachment(format: Format, load: LoadOp) -> AttachmentDescription {
+		AttachmentDescription {
+			format,
+			samples: 1,
+			load,
+			store: StoreOp::Store,
+			stencil_load: load,
+			stencil_store: StoreOp::Store,
+			initial_layout: ImageLayout::Undefined,
+			final_layout: ImageLayout::General,
+		}
+	}
+
+	fn description() -> GraphicalRenderPassDescription {
+		GraphicalRenderPassDescription {
+			attachments: vec![attachment(Format::R8G8B8A8Unorm, LoadOp::Clear), attachment(Format::R32Uint, LoadOp::Load), attachment(Format::D16Unorm, LoadOp::Clear)],
+			depth_attachment: Some(2),
+			resolve_attachments: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn test_validate() {
+		let description = description();
+
+		assert_eq!(validate(&description, &[ClearValue::Float([0.0; 4]), ClearValue::None, ClearValue::Depth(1.0)]), Ok(()));
+		assert_eq!(validate(&description, &[ClearValue::Float([0.0; 4]), ClearValue::None]), Err(ClearValuesError::CountMismatch { expected: 3, actual: 2 }));
+		assert_eq!(validate(&description, &[ClearValue::None, ClearValue::None, ClearValue::Depth(1.0)]), Err(ClearValuesError::MissingValue(0)));
+		assert_eq!(validate(&description, &[ClearValue::Float([0.0; 4]), ClearValue::Uint([0; 4]), ClearValue::Depth(1.0)]), Err(ClearValuesError::UnexpectedValue(1)));
+		assert_eq!(validate(&description, &[ClearValue::Float([0.0; 4]), ClearValue::None, ClearValue::Float([1.0; 4])]), Err(ClearValuesError::KindMismatch(2)));
+	}
+
+	#[test]
+	fn test_set_by_role() {
+		let description = description();
+
+		let values = ClearValuesBuilder::new(&description).clear_depth(1.0).clear_color(AttachmentRole::Color(0), [0.0; 4]).build();
+		assert_eq!(values.map(|values| values.len()), Ok(3));
+		let values = ClearValuesBuilder::new(&description).clear_color(AttachmentRole::Color(0), [0.0; 4]).build();
+		assert_eq!(values.err(), Some(ClearValuesError::MissingValue(2)));
+		let values = ClearValuesBuilder::new(&description).clear_color(AttachmentRole::Resolve(0), [0.0; 4]).build();
+		assert_eq!(values.err(), Some(ClearValuesError::MissingAttachment(AttachmentRole::Resolve(0))));
+	}
+}
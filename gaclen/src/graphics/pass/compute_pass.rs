@@ -0,0 +1,85 @@
+use crate::graphics::device::{Device, QueueOperation, SubmitError};
+
+use vulkano::descriptor::PipelineLayoutAbstract;
+use vulkano::descriptor::descriptor_set::{DescriptorSetsCollection, FixedSizeDescriptorSetsPool, PersistentDescriptorSet, PersistentDescriptorSetBuilder};
+use vulkano::descriptor::pipeline_layout::PipelineLayout;
+use vulkano::pipeline::{ComputePipeline, ComputePipelineAbstract, ComputePipelineCreationError};
+use vulkano::pipeline::shader::EntryPointAbstract;
+use vulkano::sync::GpuFuture;
+
+use std::sync::Arc;
+
+/// A ComputePass defines the device configuration used to execute compute (dispatch) commands.
+/// 
+/// Like [`GraphicalPass`](struct.GraphicalPass.html) it is cheap to clone and can be shared between threads.
+pub struct ComputePass<P : ?Sized> {
+	pub(in crate::graphics) pipeline: Arc<P>,
+}
+
+impl<P : ?Sized> Clone for ComputePass<P> {
+	fn clone(&self) -> Self { Self { pipeline: self.pipeline.clone() } }
+}
+
+impl ComputePass<()> {
+	/// Create a ComputePass using the entry point of a compute shader.
+	/// 
+	/// The shader should be loaded by `gaclen_shader::shader!` with `ty: "compute"`, for example `shader.main_entry_point()`.
+	pub fn new<CS>(device: &Device, shader: &CS, specialization: &CS::SpecializationConstants)
+	-> Result<ComputePass<ComputePipeline<PipelineLayout<CS::PipelineLayout>>>, ComputePipelineCreationError>
+	where
+		CS : EntryPointAbstract,
+		CS::PipelineLayout : Clone,
+	{
+		let pipeline = ComputePipeline::new(device.logical_device(), shader, specialization)?;
+		Ok(ComputePass { pipeline: Arc::new(pipeline) })
+	}
+}
+
+#[cfg(feature="expose-underlying-vulkano")]
+impl<P: ?Sized> ComputePass<P> {
+	#[inline]
+	pub fn pipeline(&self) -> &Arc<P> { &self.pipeline }
+}
+
+impl<P : ?Sized> ComputePass<P>
+where
+	P : PipelineLayoutAbstract,
+{
+	/// Start building a new persistent descriptor set, for example for binding storage buffers and images.
+	/// 
+	/// # Panic
+	/// 
+	/// Panics if the pipeline does not expect a descriptor set at given index.
+	pub fn start_persistent_descriptor_set(&self, index: usize) -> PersistentDescriptorSetBuilder<()> {
+		PersistentDescriptorSet::start(self.pipeline.descriptor_set_layout(index).unwrap().clone())
+	}
+
+	/// Allocate a pool of fixed-size descriptor sets, see [`GraphicalPass::fixed_descriptor_pool()`](struct.GraphicalPass.html#method.fixed_descriptor_pool).
+	/// 
+	/// # Panic
+	/// 
+	/// Panics if the pipeline does not expect a descriptor set at given index.
+	pub fn fixed_descriptor_pool(&self, index: usize) -> FixedSizeDescriptorSetsPool {
+		FixedSizeDescriptorSetsPool::new(self.pipeline.descriptor_set_layout(index).unwrap().clone())
+	}
+}
+
+impl<P : ?Sized> ComputePass<P>
+where
+	P : ComputePipelineAbstract + Send + Sync + 'static,
+{
+	/// Dispatch `workgroups` of the compute shader on the compute queue of the device.
+	/// 
+	/// The returned future is already flushed, wait on it before reading the results on the CPU.
+	/// 
+	/// # Panic
+	/// 
+	/// - Panics if the descriptor sets or push constants don't match the ones the pipeline expects.
+	pub fn dispatch<S, PC>(&self, device: &Device, workgroups: [u32; 3], descriptor_sets: S, push_constants: PC) -> Result<impl GpuFuture, SubmitError>
+	where
+		S : DescriptorSetsCollection,
+	{
+		let pipeline = self.pipeline.clone();
+		device.submit_oneshot(QueueOperation::Compute, |builder| builder.dispatch(workgroups, pipeline, descriptor_sets, push_constants).unwrap())
+	}
+}
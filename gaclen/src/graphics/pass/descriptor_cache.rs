@@ -0,0 +1,54 @@
+use vulkano::descriptor::DescriptorSet;
+use vulkano::descriptor::descriptor_set::{FixedSizeDescriptorSet, FixedSizeDescriptorSetBuilder, FixedSizeDescriptorSetsPool};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// A cache of descriptor sets of a single layout, keyed by the resources bound to them.
+/// 
+/// Created by [`GraphicalPass::descriptor_cache()`](struct.GraphicalPass.html#method.descriptor_cache).
+/// A set is only built when there is no set for its key yet, otherwise the existing one is reused.
+/// The key should identify the bound resources, for example the swapchain index for per-image uniform buffers:
+/// ```ignore
+/// let mut cache = pass.descriptor_cache(0);
+/// // In the main loop:
+/// let index = frame.swapchain_index();
+/// let descriptor_set = cache.get_or_build(index, |builder| builder.add_buffer(uniforms[index].clone()).unwrap().build().unwrap());
+/// ```
+/// The sets keep their resources alive, [remove](#method.remove) or [clear](#method.clear) them when the resources are replaced.
+pub struct DescriptorCache<K> {
+	pool: FixedSizeDescriptorSetsPool,
+	sets: HashMap<K, Arc<dyn DescriptorSet + Send + Sync>>,
+}
+
+impl<K : Eq + Hash> DescriptorCache<K> {
+	pub(super) fn new(pool: FixedSizeDescriptorSetsPool) -> Self { Self { pool, sets: HashMap::new() } }
+
+	/// Get the descriptor set with given key, building it with `build` if there is none.
+	pub fn get_or_build<R>(&mut self, key: K, build: impl FnOnce(FixedSizeDescriptorSetBuilder<()>) -> FixedSizeDescriptorSet<R>) -> Arc<dyn DescriptorSet + Send + Sync>
+	where
+		FixedSizeDescriptorSet<R> : DescriptorSet + Send + Sync + 'static,
+	{
+		let pool = &mut self.pool;
+		self.sets.entry(key)
+			.or_insert_with(|| Arc::new(build(pool.next())))
+			.clone()
+	}
+
+	/// Remove the descriptor set with given key, so it is built again next time.
+	/// 
+	/// Returns whether there was a set with the key.
+	pub fn remove(&mut self, key: &K) -> bool { self.sets.remove(key).is_some() }
+
+	/// Remove all descriptor sets, such as after the bound resources were recreated.
+	pub fn clear(&mut self) { self.sets.clear() }
+
+	/// Get the number of cached descriptor sets.
+	#[inline]
+	pub fn len(&self) -> usize { self.sets.len() }
+
+	/// Check whether there are no cached descriptor sets.
+	#[inline]
+	pub fn is_empty(&self) -> bool { self.sets.is_empty() }
+}
@@ -0,0 +1,226 @@
+use super::clear_values::AttachmentRole;
+use super::graphical_pass::{GraphicalPass, GraphicalRenderPassDescription};
+use crate::graphics::image::{ImageSlice, ImageSliceError};
+
+use vulkano::format::Format;
+use vulkano::framebuffer::{AttachmentsList, Framebuffer, FramebufferBuilder as VlkFramebufferBuilder, FramebufferCreationError, RenderPassAbstract};
+use vulkano::image::{ImageAccess, ImageViewAccess};
+
+use std::sync::Arc;
+
+/// A structure for building [`Framebuffers`](struct.Framebuffer.html) for a given [`GraphicalPass`](struct.GraphicalPass.html).
+///
+/// Validates the attachments as they are added, instead of failing during framebuffer creation.
+pub struct FramebufferBuilder<'a, P : ?Sized, A> {
+	pass: &'a GraphicalPass<P>,
+	builder: VlkFramebufferBuilder<Arc<P>, A>,
+	attachment_count: usize,
+	dimensions: Option<[u32; 2]>,
+}
+
+/// Error during building a framebuffer for a [`GraphicalPass`](struct.GraphicalPass.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FramebufferBuildError {
+	/// The format of the added attachment does not match the one expected by the pass.
+	FormatMismatch {
+		/// Index of the mismatched attachment.
+		index: usize,
+		/// Format the pass expects.
+		expected: Format,
+		/// Format of the added attachment.
+		actual: Format,
+	},
+	/// The dimensions of the added attachment do not match the ones of the first attachment.
+	DimensionMismatch {
+		/// Index of the mismatched attachment.
+		index: usize,
+		/// Dimensions of the first attachment.
+		expected: [u32; 2],
+		/// Dimensions of the added attachment.
+		actual: [u32; 2],
+	},
+	/// More attachments were added than the pass expects.
+	///
+	/// Contains the number of attachments the pass expects.
+	TooManyAttachments(usize),
+	/// The pass expects an attachment the [`RenderTarget`](../render_target/struct.RenderTarget.html) does not have.
+	MissingAttachment(AttachmentRole),
+	/// The [`RenderTarget`](../render_target/struct.RenderTarget.html) has an attachment the pass does not use, such as the color image of a depth-only pass.
+	UnexpectedAttachment(AttachmentRole),
+	/// The slice of an image added with [`add_view()`](struct.FramebufferBuilder.html#method.add_view) could not be created.
+	InvalidSlice {
+		/// Index of the attachment.
+		index: usize,
+		/// The reason the slice could not be created.
+		error: ImageSliceError,
+	},
+	/// Error during the creation of the framebuffer.
+	Creation(FramebufferCreationError),
+}
+
+impl<'a, P : ?Sized> FramebufferBuilder<'a, P, ()> {
+	pub(super) fn new(pass: &'a GraphicalPass<P>) -> Self {
+		Self {
+			pass,
+			builder: Framebuffer::start(pass.pipeline.clone()),
+			attachment_count: 0,
+			dimensions: None,
+		}
+	}
+}
+
+impl<'a, P, A> FramebufferBuilder<'a, P, A>
+where
+	P : RenderPassAbstract + ?Sized,
+	A : AttachmentsList,
+{
+	/// Append an attachment to the framebuffer.
+	///
+	/// Fails if the format of the attachment does not match the one the pass expects at this position.
+	/// Fails if the dimensions of the attachment differ from the ones of the first added attachment.
+	pub fn add<T>(self, attachment: T) -> Result<FramebufferBuilder<'a, P, (A, T)>, FramebufferBuildError>
+	where
+		T : ImageViewAccess,
+	{
+		let index = self.attachment_count;
+		let expected = match self.pass.description.attachments.get(index) {
+			Some(description) => description.format,
+			None => return Err(FramebufferBuildError::TooManyAttachments(self.pass.description.attachments.len())),
+		};
+		let actual = attachment.parent().format();
+		if expected != actual {
+			return Err(FramebufferBuildError::FormatMismatch { index, expected, actual });
+		}
+
+		let dimensions = {
+			let dimensions = attachment.dimensions();
+			[dimensions.width(), dimensions.height()]
+		};
+		if let Some(expected) = self.dimensions {
+			if expected != dimensions {
+				return Err(FramebufferBuildError::DimensionMismatch { index, expected, actual: dimensions });
+			}
+		}
+
+		Ok(FramebufferBuilder {
+			pass: self.pass,
+			builder: self.builder.add(attachment)?,
+			attachment_count: index + 1,
+			dimensions: Some(dimensions),
+		})
+	}
+
+	/// Append a single mip level and array layer of an image as an attachment to the framebuffer.
+	///
+	/// Allows rendering to a face of a cubemap, a level of a mip chain or a layer of a texture array.
+	/// Fails if the image does not have the requested level or layer, otherwise same as [`add()`](#method.add).
+	pub fn add_view<I>(self, image: I, mip_level: u32, array_layer: u32) -> Result<FramebufferBuilder<'a, P, (A, ImageSlice<I>)>, FramebufferBuildError>
+	where
+		I : ImageAccess + ImageViewAccess,
+	{
+		let index = self.attachment_count;
+		let slice = ImageSlice::new(image, mip_level, array_layer).map_err(|error| FramebufferBuildError::InvalidSlice { index, error })?;
+		self.add(slice)
+	}
+
+	/// Build the framebuffer.
+	pub fn build(self) -> Result<Framebuffer<Arc<P>, A>, FramebufferBuildError> {
+		Ok(self.builder.build()?)
+	}
+}
+
+// Order in which a pass expects the images of a render target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TargetOrder {
+	Color,
+	ColorDepth,
+	DepthColor,
+}
+
+// Find the order of the color and optional depth images of a render target expected by a pass.
+pub(super) fn target_order(description: &GraphicalRenderPassDescription, has_depth: bool) -> Result<TargetOrder, FramebufferBuildError> {
+	if !description.resolve_attachments.is_empty() {
+		return Err(FramebufferBuildError::MissingAttachment(AttachmentRole::Resolve(0)));
+	}
+	match description.color_attachment_indices().count() {
+		0 => return Err(FramebufferBuildError::UnexpectedAttachment(AttachmentRole::Color(0))),
+		1 => (),
+		_ => return Err(FramebufferBuildError::MissingAttachment(AttachmentRole::Color(1))),
+	};
+	match (description.depth_attachment, has_depth) {
+		(None, false) => Ok(TargetOrder::Color),
+		(None, true) => Err(FramebufferBuildError::TooManyAttachments(description.attachments.len())),
+		(Some(_), false) => Err(FramebufferBuildError::MissingAttachment(AttachmentRole::Depth)),
+		(Some(0), true) => Ok(TargetOrder::DepthColor),
+		(Some(_), true) => Ok(TargetOrder::ColorDepth),
+	}
+}
+
+impl From<FramebufferCreationError> for FramebufferBuildError {
+	fn from(err: FramebufferCreationError) -> Self { Self::Creation(err) }
+}
+
+impl std::fmt::Display for FramebufferBuildError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::FormatMismatch { index, expected, actual } => write!(fmt, "the attachment at index {} has format {:?}, but the pass expects {:?}", index, actual, expected),
+			Self::DimensionMismatch { index, expected, actual } => write!(fmt, "the attachment at index {} has dimensions {:?}, but the first attachment has {:?}", index, actual, expected),
+			Self::TooManyAttachments(count) => write!(fmt, "too many attachments, the pass expects {}", count),
+			Self::MissingAttachment(role) => write!(fmt, "the pass expects a {:?} attachment, but the render target has none", role),
+			Self::UnexpectedAttachment(role) => write!(fmt, "the render target has a {:?} attachment, but the pass does not use one", role),
+			Self::InvalidSlice { index, error } => write!(fmt, "invalid attachment at index {}: {}", index, error),
+			Self::Creation(err) => write!(fmt, "failed to create the framebuffer: {}", err),
+		}
+	}
+}
+impl std::error::Error for FramebufferBuildError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Creation(err) => Some(err),
+			Self::InvalidSlice { error, .. } => Some(error),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vulkano::framebuffer::{AttachmentDescription, LoadOp, StoreOp};
+	use vulkano::image::ImageLayout;
+
+	fn description(formats: &[Format], depth_attachment: Option<usize>) -> GraphicalRenderPassDescription {
+		let attachments = formats.iter().map(|&format| AttachmentDescription {
+			format,
+			samples: 1,
+			load: LoadOp::Clear,
+			store: StoreOp::Store,
+			stencil_load: LoadOp::DontCare,
+			stencil_store: StoreOp::DontCare,
+			initial_layout: ImageLayout::Undefined,
+			final_layout: ImageLayout::General,
+		}).collect();
+		GraphicalRenderPassDescription { attachments, depth_attachment, resolve_attachments: Vec::new() }
+	}
+
+	#[test]
+	fn test_target_order() {
+		let color = description(&[Format::R8G8B8A8Unorm], None);
+		assert_eq!(target_order(&color, false), Ok(TargetOrder::Color));
+		assert_eq!(target_order(&color, true), Err(FramebufferBuildError::TooManyAttachments(1)));
+
+		let color_depth = description(&[Format::R8G8B8A8Unorm, Format::D16Unorm], Some(1));
+		assert_eq!(target_order(&color_depth, true), Ok(TargetOrder::ColorDepth));
+		assert_eq!(target_order(&color_depth, false), Err(FramebufferBuildError::MissingAttachment(AttachmentRole::Depth)));
+
+		let depth_color = description(&[Format::D16Unorm, Format::R8G8B8A8Unorm], Some(0));
+		assert_eq!(target_order(&depth_color, true), Ok(TargetOrder::DepthColor));
+
+		let two_colors = description(&[Format::R8G8B8A8Unorm, Format::R8G8B8A8Unorm], None);
+		assert_eq!(target_order(&two_colors, false), Err(FramebufferBuildError::MissingAttachment(AttachmentRole::Color(1))));
+
+		let depth_only = description(&[Format::D16Unorm], Some(0));
+		assert_eq!(target_order(&depth_only, true), Err(FramebufferBuildError::UnexpectedAttachment(AttachmentRole::Color(0))));
+		assert_eq!(target_order(&depth_only, false), Err(FramebufferBuildError::UnexpectedAttachment(AttachmentRole::Color(0))));
+	}
+}
@@ -1,17 +1,56 @@
-use super::builder::GraphicalPassBuilder;
+use super::builder::{GraphicalPassBuilder, build_pipeline};
+use super::clear_values::{self, ClearValuesBuilder, ClearValuesError};
+use super::descriptor_cache::DescriptorCache;
+use super::framebuffer::{self, FramebufferBuilder, FramebufferBuildError, TargetOrder};
+use crate::graphics::device::{CommandContext, Device};
+use crate::graphics::frame::{SecondaryCommandsBuilder, Viewport};
+use crate::graphics::render_target::RenderTarget;
 
 use vulkano::descriptor::PipelineLayoutAbstract;
 use vulkano::descriptor::descriptor_set::{FixedSizeDescriptorSetsPool, PersistentDescriptorSet, PersistentDescriptorSetBuilder};
-use vulkano::format::ClearValue;
-use vulkano::framebuffer::{Framebuffer, FramebufferBuilder};
-use vulkano::framebuffer::{AttachmentDescription, PassDescription, RenderPassDesc, RenderPassDescClearValues, PassDependencyDescription};
+use vulkano::format::{ClearValue, Format};
+use vulkano::framebuffer::{AttachmentDescription, FramebufferAbstract, PassDescription, RenderPass, RenderPassAbstract, RenderPassDesc, RenderPassDescClearValues, PassDependencyDescription};
 use vulkano::image::ImageLayout;
+use vulkano::pipeline::{GraphicsPipelineAbstract, GraphicsPipelineCreationError};
+use vulkano::pipeline::blend::{AttachmentBlend, LogicOp};
+use vulkano::pipeline::depth_stencil::DepthStencil;
+use vulkano::pipeline::input_assembly::PrimitiveTopology;
+use vulkano::pipeline::raster::Rasterization;
+use vulkano::pipeline::shader::{GraphicsEntryPointAbstract, SpecializationConstants};
+use vulkano::pipeline::vertex::VertexDefinition;
 
 use std::sync::Arc;
 
 /// A GraphicalPass defines the device configuration used to execute draw commands.
+/// 
+/// The pass is `Send` and `Sync` for thread-safe pipelines (such as the one built by [`GraphicalPassBuilder::build()`](struct.GraphicalPassBuilder.html#method.build)).
+/// Cloning is cheap, since it only clones references to the underlying pipeline and render pass, which allows sharing the pass with threads recording [secondary commands](#method.start_secondary_commands).
 pub struct GraphicalPass<P : ?Sized> {
 	pub(in crate::graphics) pipeline: Arc<P>,
+	pub(in crate::graphics) render_pass: Arc<RenderPass<GraphicalRenderPassDescription>>,
+	pub(in crate::graphics) state: PipelineState,
+	pub(in crate::graphics) description: GraphicalRenderPassDescription,
+}
+
+impl<P : ?Sized> Clone for GraphicalPass<P> {
+	fn clone(&self) -> Self {
+		Self {
+			pipeline: self.pipeline.clone(),
+			render_pass: self.render_pass.clone(),
+			state: self.state.clone(),
+			description: self.description.clone(),
+		}
+	}
+}
+
+impl<P : ?Sized> std::fmt::Debug for GraphicalPass<P> {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		fmt.debug_struct("GraphicalPass")
+			.field("attachment_count", &self.description.attachments.len())
+			.field("attachment_formats", &self.attachment_formats())
+			.field("depth_attachment", &self.description.depth_attachment)
+			.finish()
+	}
 }
 
 impl GraphicalPass<()> {
@@ -27,6 +66,10 @@ impl<P: ?Sized> GraphicalPass<P> {
 
 impl<P : ?Sized> GraphicalPass<P>
 {
+	/// Check whether the other pass uses the same pipeline, for example because it is a clone of this one.
+	#[inline]
+	pub fn shares_pipeline(&self, other: &GraphicalPass<P>) -> bool { Arc::ptr_eq(&self.pipeline, &other.pipeline) }
+
 	/// Start building a new persistent descriptor set.
 	/// 
 	/// # Panic
@@ -41,28 +84,182 @@ impl<P : ?Sized> GraphicalPass<P>
 
 	/// Allocate a pool of fixed-size descriptor sets.
 	/// 
+	/// The pool reuses the memory of descriptor sets that are no longer in use.
+	/// Prefer it over [persistent descriptor sets](#method.start_persistent_descriptor_set) for sets that change every frame (such as ones with per-frame uniform buffers), to avoid allocating memory every frame:
+	/// ```ignore
+	/// let mut pool = pass.fixed_descriptor_pool(0);
+	/// // In the main loop:
+	/// let uniform = uniform_buffer_pool.next(data).unwrap();
+	/// let descriptor_set = pool.next().add_buffer(uniform).unwrap().build().unwrap();
+	/// ```
+	/// 
 	/// # Panic
 	/// 
 	/// Panics if the pipeline does not expect a descriptor set at given index.
-	pub fn create_fixed_size_descriptor_set_pool(&self, index: usize) -> FixedSizeDescriptorSetsPool
+	pub fn fixed_descriptor_pool(&self, index: usize) -> FixedSizeDescriptorSetsPool
 	where
 		P : PipelineLayoutAbstract,
 	{
 		FixedSizeDescriptorSetsPool::new(self.pipeline.descriptor_set_layout(index).unwrap().clone())
 	}
 
+	/// Allocate a pool of fixed-size descriptor sets.
+	/// 
+	/// Same as [`fixed_descriptor_pool()`](#method.fixed_descriptor_pool).
+	#[deprecated(since = "0.0.14", note = "use `fixed_descriptor_pool()` instead")]
+	#[inline]
+	pub fn create_fixed_size_descriptor_set_pool(&self, index: usize) -> FixedSizeDescriptorSetsPool
+	where
+		P : PipelineLayoutAbstract,
+	{
+		self.fixed_descriptor_pool(index)
+	}
+
+	/// Create a cache of descriptor sets, which only builds sets for bindings that were not used before.
+	/// 
+	/// Prefer it over a [pool](#method.fixed_descriptor_pool) for sets that are bound to a few long-lived resources (such as one uniform buffer per swapchain image), see [`DescriptorCache`](struct.DescriptorCache.html).
+	/// 
+	/// # Panic
+	/// 
+	/// Panics if the pipeline does not expect a descriptor set at given index.
+	pub fn descriptor_cache<K : Eq + std::hash::Hash>(&self, index: usize) -> DescriptorCache<K>
+	where
+		P : PipelineLayoutAbstract,
+	{
+		DescriptorCache::new(self.fixed_descriptor_pool(index))
+	}
+
 	/// Start building a framebuffer for this pass.
-	pub fn start_framebuffer(&self) -> FramebufferBuilder<Arc<P>, ()> {
-		Framebuffer::start(self.pipeline.clone())
+	/// 
+	/// The attachments should be added in the same order they were added to the [`GraphicalPassBuilder`](struct.GraphicalPassBuilder.html).
+	pub fn start_framebuffer(&self) -> FramebufferBuilder<P, ()> {
+		FramebufferBuilder::new(self)
+	}
+
+	/// Build a framebuffer for drawing to a [`RenderTarget`](../render_target/struct.RenderTarget.html) using this pass.
+	/// 
+	/// The images of the target are added in the order the pass expects them, regardless of whether the depth attachment was added before or after the color one.
+	/// Fails with [`FramebufferBuildError::MissingAttachment`](enum.FramebufferBuildError.html#variant.MissingAttachment) if the pass expects an attachment the target does not have (such as a second color or a resolve one), or [`FramebufferBuildError::TooManyAttachments`](enum.FramebufferBuildError.html#variant.TooManyAttachments) if the target has a depth image the pass does not use.
+	/// Depth-only passes are not supported, as the target always has a color image, they fail with [`FramebufferBuildError::UnexpectedAttachment`](enum.FramebufferBuildError.html#variant.UnexpectedAttachment).
+	/// The formats of the images are validated as well.
+	pub fn framebuffer_for(&self, target: &RenderTarget) -> Result<Arc<dyn FramebufferAbstract + Send + Sync>, FramebufferBuildError>
+	where
+		P : RenderPassAbstract + Send + Sync + 'static,
+	{
+		let order = framebuffer::target_order(&self.description, target.depth_image().is_some())?;
+		let color = target.color_image();
+		match (order, target.depth_image()) {
+			(TargetOrder::ColorDepth, Some(depth)) => Ok(Arc::new(self.start_framebuffer().add(color)?.add(depth)?.build()?)),
+			(TargetOrder::DepthColor, Some(depth)) => Ok(Arc::new(self.start_framebuffer().add(depth)?.add(color)?.build()?)),
+			_ => Ok(Arc::new(self.start_framebuffer().add(color)?.build()?)),
+		}
+	}
+
+	/// Start building clear values for using this pass in a frame.
+	/// 
+	/// The values should be added in the same order as the attachments of this pass.
+	pub fn clear_values(&self) -> ClearValuesBuilder {
+		ClearValuesBuilder::new(&self.description)
+	}
+
+	/// Check that clear values match the attachments of this pass, before using them in [`Frame::begin_pass()`](../frame/struct.Frame.html#method.begin_pass).
+	/// 
+	/// Attachments that use `LoadOp::Clear` require a value, others require `ClearValue::None`.
+	/// Values built with [`GraphicalPass::clear_values()`](#method.clear_values) are already validated.
+	pub fn validate_clear_values(&self, values: &[ClearValue]) -> Result<(), ClearValuesError> {
+		clear_values::validate(&self.description, values)
+	}
+
+	/// Get the formats of the attachments of this pass.
+	/// 
+	/// The formats are in the same order the attachments are expected to be added to a framebuffer.
+	pub fn attachment_formats(&self) -> Vec<Format> {
+		self.description.attachments.iter().map(|attachment| attachment.format).collect()
+	}
+}
+
+impl<P : ?Sized> GraphicalPass<P>
+where
+	P : GraphicsPipelineAbstract + Send + Sync + 'static,
+{
+	/// Start recording secondary commands that draw using this pass.
+	/// 
+	/// The recording may happen on a different thread, the result should then be executed within a [`PassInFrame`](../frame/struct.PassInFrame.html) using this pass.
+	/// 
+	/// # Panic
+	/// 
+	/// - Panics if fails to create the command buffer.
+	pub fn start_secondary_commands(&self, context: &CommandContext, viewport: Viewport) -> SecondaryCommandsBuilder<P> {
+		SecondaryCommandsBuilder::new(self, context, viewport)
 	}
 }
 
+impl GraphicalPass<dyn GraphicsPipelineAbstract + Send + Sync + 'static> {
+	/// Rebuild the pipeline of this pass with new shaders, keeping the render pass.
+	/// 
+	/// The rest of the configuration (topology, rasterization, depth test) is kept, while the vertex input has to be provided again since it depends on the vertex shader.
+	/// Framebuffers created for this pass remain valid, which makes this useful for reloading shaders or changing quality settings.
+	/// The pass is left unchanged if the new pipeline fails to build.
+	pub fn rebuild_pipeline<VI, VS, VSS, FS, FSS>(
+		&mut self,
+		device: &Device,
+		vertex_input: VI,
+		vertex_shader: VS,
+		vertex_specialization: VSS,
+		fragment_shader: FS,
+		fragment_specialization: FSS,
+	) -> Result<(), GraphicsPipelineCreationError>
+	where
+		VS : GraphicsEntryPointAbstract<SpecializationConstants=VSS>,
+		FS : GraphicsEntryPointAbstract<SpecializationConstants=FSS>,
+		VSS : SpecializationConstants,
+		FSS : SpecializationConstants,
+		VS::PipelineLayout : Send + Sync + Clone + 'static,
+		FS::PipelineLayout : Send + Sync + Clone + 'static,
+		VI : VertexDefinition<VS::InputDefinition> + Send + Sync + 'static,
+	{
+		self.pipeline = build_pipeline(
+			device,
+			vertex_input,
+			(vertex_shader, vertex_specialization),
+			(fragment_shader, fragment_specialization),
+			&self.state,
+			self.render_pass.clone(),
+		)?;
+		Ok(())
+	}
+}
+
+/// Fixed-function configuration of a pipeline, kept for rebuilding it.
+#[derive(Debug, Clone)]
+pub(in crate::graphics) struct PipelineState {
+	pub primitive_topology: PrimitiveTopology,
+	pub primitive_restart: bool,
+	pub rasterization: Rasterization,
+	pub depth_stencil: DepthStencil,
+	pub blend: AttachmentBlend,
+	pub logic_op: Option<LogicOp>,
+	pub sample_shading: Option<f32>,
+	pub alpha_to_coverage: bool,
+	pub viewport_count: u32,
+}
+
 #[derive(Debug, Clone)]
 pub(in crate::graphics) struct GraphicalRenderPassDescription {
 	/// Image attachments of the render pass.
 	pub attachments: Vec<AttachmentDescription>,
 	/// Depth stencil attachment index.
 	pub depth_attachment: Option<usize>,
+	/// Indices of attachments the color attachments are resolved into.
+	pub resolve_attachments: Vec<usize>,
+}
+
+impl GraphicalRenderPassDescription {
+	/// Get the indices of color attachments (neither depth nor resolve ones).
+	pub fn color_attachment_indices<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
+		(0..self.attachments.len())
+			.filter(move |index| self.depth_attachment != Some(*index) && !self.resolve_attachments.contains(index))
+	}
 }
 
 unsafe impl RenderPassDesc for GraphicalRenderPassDescription {
@@ -83,33 +280,21 @@ unsafe impl RenderPassDesc for GraphicalRenderPassDescription {
 	#[inline]
 	fn subpass_desc(&self, num: usize) -> Option<PassDescription> {
 		if num == 0 {
-			let color_attachments = {
-				if let Some(depth_index) = self.depth_attachment {
-					let mut color_attachments = Vec::with_capacity(self.attachments.len() - 1);
-					for i in 0..depth_index {
-						color_attachments.push((i, ImageLayout::ColorAttachmentOptimal));
-					}
-					for i in depth_index + 1 .. self.attachments.len() {
-						color_attachments.push((i, ImageLayout::ColorAttachmentOptimal));
-					}
-					color_attachments
-				} else {
-					let mut color_attachments = Vec::with_capacity(self.attachments.len());
-					for i in 0..self.attachments.len() {
-						color_attachments.push((i, ImageLayout::ColorAttachmentOptimal))
-					};
-					color_attachments
-				}
-			};
+			let color_attachments = self.color_attachment_indices()
+				.map(|index| (index, ImageLayout::ColorAttachmentOptimal))
+				.collect();
 			let depth_stencil = match self.depth_attachment {
 				Some(index) => Some((index, ImageLayout::DepthStencilAttachmentOptimal)),
 				None => None,
 			};
+			let resolve_attachments = self.resolve_attachments.iter()
+				.map(|&index| (index, ImageLayout::ColorAttachmentOptimal))
+				.collect();
 			Some(PassDescription{
 				color_attachments,
 				depth_stencil,
 				input_attachments: Vec::default(),
-				resolve_attachments: Vec::default(),
+				resolve_attachments,
 				preserve_attachments: Vec::default(),
 			})
 		} else {
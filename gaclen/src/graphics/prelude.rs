@@ -0,0 +1,21 @@
+//! Prelude re-exports the most commonly used graphics types, for importing them at once:
+//! ```
+//! use gaclen::graphics::prelude::*;
+//! ```
+//! 
+//! Each type is re-exported from its canonical path:
+//! - [`Format`](../format/enum.Format.html) from [`format`](../format/index.html), which [`image`](../image/index.html) also re-exports.
+//! - [`PresentMode`](../enum.PresentMode.html) from the [`graphics`](../index.html) root, along with [`PresentModeExt`](../trait.PresentModeExt.html) for describing it.
+//! - [`Device`](../device/struct.Device.html) from [`device`](../device/index.html), not to be confused with the underlying `vulkano::device::Device`.
+//! 
+//! Both [`color`](../color/index.html) and [`swapchain`](../swapchain/index.html) have a `ColorSpace`, neither is included to avoid ambiguity.
+
+pub use super::{bootstrap, PresentMode, PresentModeExt, ResizeError};
+pub use super::buffer::BufferUsage;
+pub use super::context::Context;
+pub use super::device::Device;
+pub use super::format::Format;
+pub use super::frame::{BeginResult, FlushError, Frame, FrameFinishError, Viewport};
+pub use super::pass::{ComputePass, GraphicalPass, LoadOp, StoreOp};
+pub use super::swapchain::Swapchain;
+pub use super::timing::FrameClock;
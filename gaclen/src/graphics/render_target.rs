@@ -0,0 +1,119 @@
+//! Render targets are images that can be drawn to and then sampled or copied, for example for post-processing.
+//!
+//! A [`RenderTarget`](struct.RenderTarget.html) owns a color image and optionally a depth image of the same size.
+//! To draw to it, build a framebuffer for a [`GraphicalPass`](../pass/struct.GraphicalPass.html) with a color attachment and a depth one if the target has depth, the images are added in the order the pass expects:
+//! ```ignore
+//! let pass = GraphicalPass::start()
+//! 	// ...
+//! 	.add_color_target(target.color_format())?
+//! 	.build(&device)?;
+//! let framebuffer = target.framebuffer(&pass)?;
+//! let frame = frame.begin_pass(&pass, framebuffer, target.viewport(), clear_values);
+//! ```
+//! The framebuffer should be rebuilt after the target is [resized](struct.RenderTarget.html#method.resize).
+
+use super::device::Device;
+use super::frame::Viewport;
+use super::pass::{FramebufferBuildError, GraphicalPass};
+
+use std::sync::Arc;
+
+use vulkano::format::Format;
+use vulkano::framebuffer::{FramebufferAbstract, RenderPassAbstract};
+use vulkano::image::{AttachmentImage, ImageCreationError, ImageUsage};
+
+/// A color image with an optional depth image that can be drawn to and sampled afterwards.
+pub struct RenderTarget {
+	color: Arc<AttachmentImage>,
+	depth: Option<Arc<AttachmentImage>>,
+	color_format: Format,
+	depth_format: Option<Format>,
+	dimensions: [u32; 2],
+}
+
+impl RenderTarget {
+	/// Create a new render target with images of given dimensions and formats.
+	/// 
+	/// The images can be sampled and used as source of transfer operations.
+	pub fn new(
+		device: &Device,
+		dimensions: [u32; 2],
+		color_format: Format,
+		depth_format: impl Into<Option<Format>>,
+	) -> Result<RenderTarget, ImageCreationError>
+	{
+		let depth_format = depth_format.into();
+		let (color, depth) = create_images(device, dimensions, color_format, depth_format)?;
+		Ok(RenderTarget { color, depth, color_format, depth_format, dimensions })
+	}
+
+	/// Reallocate the images of the render target with new dimensions.
+	/// 
+	/// Does nothing if the dimensions did not change.
+	/// Previously built framebuffers keep using the old images, so they should be rebuilt.
+	pub fn resize(&mut self, device: &Device, dimensions: [u32; 2]) -> Result<(), ImageCreationError> {
+		if dimensions == self.dimensions { return Ok(()); }
+
+		let (color, depth) = create_images(device, dimensions, self.color_format, self.depth_format)?;
+		self.color = color;
+		self.depth = depth;
+		self.dimensions = dimensions;
+		Ok(())
+	}
+
+	/// Build a framebuffer for drawing to this render target using given pass.
+	/// 
+	/// Same as [`GraphicalPass::framebuffer_for()`](../pass/struct.GraphicalPass.html#method.framebuffer_for).
+	#[inline]
+	pub fn framebuffer<P>(&self, pass: &GraphicalPass<P>) -> Result<Arc<dyn FramebufferAbstract + Send + Sync>, FramebufferBuildError>
+	where
+		P : RenderPassAbstract + Send + Sync + ?Sized + 'static,
+	{
+		pass.framebuffer_for(self)
+	}
+
+	/// Get a viewport covering the whole render target.
+	pub fn viewport(&self) -> Viewport {
+		Viewport {
+			origin: [0.0, 0.0],
+			dimensions: [self.dimensions[0] as f32, self.dimensions[1] as f32],
+			depth_range: 0.0 .. 1.0,
+		}
+	}
+
+	/// Get the color image, which can be sampled after drawing.
+	#[inline]
+	pub fn color_image(&self) -> Arc<AttachmentImage> { self.color.clone() }
+
+	/// Get the depth image, if the render target has one.
+	#[inline]
+	pub fn depth_image(&self) -> Option<Arc<AttachmentImage>> { self.depth.clone() }
+
+	/// Get the format of the color image.
+	#[inline]
+	pub fn color_format(&self) -> Format { self.color_format }
+
+	/// Get the format of the depth image, if the render target has one.
+	#[inline]
+	pub fn depth_format(&self) -> Option<Format> { self.depth_format }
+
+	/// Get the dimensions of the images in pixels.
+	#[inline]
+	pub fn dimensions(&self) -> [u32; 2] { self.dimensions }
+}
+
+fn create_images(
+	device: &Device,
+	dimensions: [u32; 2],
+	color_format: Format,
+	depth_format: Option<Format>,
+) -> Result<(Arc<AttachmentImage>, Option<Arc<AttachmentImage>>), ImageCreationError>
+{
+	let usage = ImageUsage { sampled: true, transfer_source: true, .. ImageUsage::none() };
+	let color = AttachmentImage::with_usage(device.logical_device(), dimensions, color_format, usage)?;
+	let depth = match depth_format {
+		Some(format) => Some(AttachmentImage::with_usage(device.logical_device(), dimensions, format, usage)?),
+		None => None,
+	};
+	Ok((color, depth))
+}
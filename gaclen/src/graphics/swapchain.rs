@@ -3,12 +3,46 @@
 //! Main usage for *swapchains* is in [`Frame`](struct.Frame.html) [initialization](struct.Frame.html#method.begin) and they determine the resolution of the image that will be drawn.
 //! To draw an image that will then be presented use [`Swapchain::get_image_for()`](struct.Swapchain.html#method.get_color_image_for) when building the [`Framebuffer`](struct.Framebuffer.html) for a pass that will draw presented results.
 //! 
-//! **Gaclen**'s [`Swapchain`s](struct.Swapchain.html) currently also include [depth buffers](https://en.wikipedia.org/wiki/Z-buffering) that match the size of the image, this functionality however might change in the near future.
+//! # Per-frame resources.
+//! 
+//! The GPU may still be reading resources of previous frames while the next one is recorded.
+//! Data that is written every frame (such as uniform buffers) should therefore have [`image_count()`](struct.Swapchain.html#method.image_count) copies, indexed by [`Frame::swapchain_index()`](../frame/struct.Frame.html#method.swapchain_index):
+//! ```ignore
+//! let uniforms: Vec<_> = (0..swapchain.image_count()).map(|_| device.create_uniform_buffer(data)).collect();
+//! // In the main loop:
+//! let frame = Frame::begin(device, &swapchain)?;
+//! let uniform = &uniforms[frame.swapchain_index()];
+//! ```
+//! A [`CpuBufferPool`](../buffer/struct.CpuBufferPool.html) handles this automatically, at the cost of allocating buffers as needed.
+//! 
+//! Framebuffers of passes that draw to the swapchain the same way every frame can be built once per image with [`build_framebuffers()`](struct.Swapchain.html#method.build_framebuffers) (or from [`color_images()`](struct.Swapchain.html#method.color_images)) instead of every frame:
+//! ```ignore
+//! let mut framebuffers = swapchain.build_framebuffers(&pass)?;
+//! // In the main loop, after a resize:
+//! framebuffers = swapchain.build_framebuffers(&pass)?;
+//! // When drawing:
+//! let framebuffer = framebuffers[frame.swapchain_index()].clone();
+//! ```
+//! The framebuffers reference the images, so they must be rebuilt after every [resize](struct.Swapchain.html#method.resize).
+//! 
+//! # Sampling the images.
+//! 
+//! Screen-space and feedback effects may sample the image drawn by a previous pass of the same frame.
+//! This requires the `sampled` usage, which can be requested as [optional](struct.SwapchainConfig.html#structfield.optional_usage) since not all surfaces support it.
+//! The image is then available through [`Swapchain::sampled_image_for()`](struct.Swapchain.html#method.sampled_image_for) and can be added to a descriptor set of a later pass that does not draw to it.
+//! The layout transitions between drawing to, sampling and presenting the image are inserted automatically when recording the frame.
+//! If the surface does not support sampling, draw to a [`RenderTarget`](../render_target/struct.RenderTarget.html) instead and copy (or draw) the result to the swapchain image.
+//! 
+//! Every window requires its own swapchain, but all of them can be created from and drawn using the same [`Device`](../device/struct.Device.html).
+//! 
+//! **Gaclen**'s [`Swapchain`s](struct.Swapchain.html) optionally include [depth buffers](https://en.wikipedia.org/wiki/Z-buffering) that match the size of the image, this functionality however might change in the near future.
 
-use super::ResizeError;
+use super::{PresentMode, ResizeError};
 use super::context::Context;
-use super::device::Device;
+use super::device::{Device, FamilySharing};
+use super::format::is_srgb;
 use super::frame::Frame;
+use super::pass::{FramebufferBuildError, GraphicalPass};
 
 use winit::window::Window;
 
@@ -17,13 +51,51 @@ use std::sync::Arc;
 use vulkano::command_buffer::DynamicState;
 use vulkano::device::{Device as LogicalDevice, Queue as DeviceQueue};
 use vulkano::format::Format;
-use vulkano::image::{AttachmentImage, SwapchainImage, ImageCreationError};
+use vulkano::framebuffer::{FramebufferAbstract, RenderPassAbstract};
+use vulkano::image::{AttachmentImage, SwapchainImage, ImageCreationError, ImageUsage};
 use vulkano::swapchain::{Surface, Swapchain as VlkSwapchain, SwapchainCreationError as VlkSwapchainCreationError};
 use vulkano::pipeline::viewport::Viewport;
+use vulkano::sync::SharingMode;
 
-pub use vulkano::swapchain::PresentMode;
+pub use vulkano::swapchain::ColorSpace;
 
-type ImageFormat = (Format, vulkano::swapchain::ColorSpace);
+/// A format of swapchain images along with the color space they are presented in.
+pub type ImageFormat = (Format, ColorSpace);
+
+/// Configuration used for creating a [`Swapchain`](struct.Swapchain.html) with [`Swapchain::with_config()`](struct.Swapchain.html#method.with_config).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapchainConfig {
+	/// Additional usage flags of the images, besides `color_attachment`.
+	/// 
+	/// Default is no additional usage.
+	pub usage: ImageUsage,
+	/// Usage flags of the images that are only set if supported by the draw-surface, such as `sampled`.
+	/// 
+	/// Check the actual usage with [`Swapchain::usage()`](struct.Swapchain.html#method.usage).
+	/// Default is no optional usage.
+	pub optional_usage: ImageUsage,
+	/// Allow the presentation engine to discard pixels that are not visible (such as ones covered by other windows).
+	/// 
+	/// Clipped pixels have undefined contents, which breaks reading the images back (such as for screenshots).
+	/// If `None` the images are clipped unless they have the `transfer_source` usage, including when it was requested as optional and is supported.
+	/// Default is `None`.
+	pub clipped: Option<bool>,
+	/// How the images are shared between queue families of the device.
+	/// 
+	/// Default is [`ImageSharing::Exclusive`](enum.ImageSharing.html#variant.Exclusive).
+	pub sharing: ImageSharing,
+}
+
+/// Sharing mode of swapchain images between queue families of a [`Device`](../device/struct.Device.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageSharing {
+	/// The images are owned by the graphics queue family, which draws to and presents them.
+	Exclusive,
+	/// The images are shared between all queue families of the device, such as for writing them from a compute queue of a different family.
+	/// 
+	/// Concurrent sharing may be slower, it falls back to exclusive sharing if the device uses a single queue family.
+	Concurrent,
+}
 
 /// Swapchain is the infrastructure for drawing on the screen.
 /// 
@@ -34,7 +106,9 @@ pub struct Swapchain {
 	pub(super) swapchain: Arc<VlkSwapchain<Arc<Window>>>,
 	pub(super) images: Vec<Arc<SwapchainImage<Arc<Window>>>>,
 	pub(super) depths: Vec<Arc<AttachmentImage>>,
-	pub(super) depth_format: Format,
+	pub(super) color_space: ColorSpace,
+	pub(super) usage: ImageUsage,
+	pub(super) depth_format: Option<Format>,
 	pub(super) inverse_depth: bool,
 
 	pub(super) dynamic_state: DynamicState,
@@ -54,46 +128,139 @@ pub enum SwapchainCreationError {
 	Image(ImageCreationError),
 	/// No applicable format for draw-surface was found.
 	NoCompatibleFormatFound,
-	/// Window passed for the creation of the device has no apparent size..
+	/// Window passed for the creation of the swapchain has no apparent size, typically because it is minimized.
 	UnsizedWindow,
+	/// Some of the requested image usage flags are not supported by the draw-surface.
+	/// 
+	/// Contains the unsupported flags.
+	UnsupportedUsage(ImageUsage),
+	/// The format selector picked a format that is not supported by the draw-surface.
+	/// 
+	/// Contains the picked format.
+	UnsupportedFormat(ImageFormat),
+	/// The draw-surface reports no supported composite alpha modes, which happens on some headless or virtualized devices.
+	NoCompositeAlpha,
+	/// The requested present mode is not supported by the draw-surface.
+	/// 
+	/// Contains the requested present mode.
+	UnsupportedPresentMode(PresentMode),
 }
 
 impl Swapchain {
 	/// Create a new Swapchain using provided Device.
+	/// 
+	/// The images will be sized to the physical size (in pixels) of the window.
+	/// If `depth_format` is `None` the swapchain will not have depth images.
+	/// If `inverse_depth` is true the depth range of the viewport is inverted, see [`Swapchain::inverse_depth()`](#method.inverse_depth).
+	/// Fails with [`SwapchainCreationError::UnsizedWindow`](enum.SwapchainCreationError.html#variant.UnsizedWindow) if the window has no size, such as when it is minimized.
+	/// 
+	/// The images can only be used as color attachments, use [`Swapchain::with_usage()`](#method.with_usage) for other uses.
 	pub fn new(
 		context: &Context,
 		device: &Device,
 		window: Arc<Window>,
 		present_mode: PresentMode,
-		depth_format: Format,
+		depth_format: impl Into<Option<Format>>,
+		inverse_depth: bool,
+	) -> Result<Swapchain, SwapchainCreationError>
+	{
+		Swapchain::with_usage(context, device, window, present_mode, depth_format, inverse_depth, ImageUsage::none())
+	}
+
+	/// Create a new Swapchain using provided Device, with the format of images picked by `format_selector`.
+	/// 
+	/// Same as [`Swapchain::new()`](#method.new), but `format_selector` is given all formats supported by the draw-surface and should return one of them.
+	/// Fails with [`SwapchainCreationError::UnsupportedFormat`](enum.SwapchainCreationError.html#variant.UnsupportedFormat) if the picked format is not one of the supported ones.
+	/// The default selector is [`select_srgb_format()`](fn.select_srgb_format.html), use [`select_hdr_format()`](fn.select_hdr_format.html) for HDR output.
+	/// 
+	/// Color spaces other than `ColorSpace::SrgbNonLinear` are only available if the [context supports them](../context/struct.Context.html#method.supports_extended_color_spaces) and the display does.
+	pub fn new_with(
+		context: &Context,
+		device: &Device,
+		window: Arc<Window>,
+		present_mode: PresentMode,
+		depth_format: impl Into<Option<Format>>,
+		inverse_depth: bool,
+		format_selector: impl Fn(&[ImageFormat]) -> ImageFormat,
+	) -> Result<Swapchain, SwapchainCreationError>
+	{
+		Swapchain::create(context, device, window, present_mode, depth_format.into(), inverse_depth, &SwapchainConfig::default(), format_selector)
+	}
+
+	/// Create a new Swapchain using provided Device, with images that can be used in additional ways.
+	/// 
+	/// Same as [`Swapchain::new()`](#method.new), but the images additionally have the `usage` flags set.
+	/// Fails with [`SwapchainCreationError::UnsupportedUsage`](enum.SwapchainCreationError.html#variant.UnsupportedUsage) if any of the flags is not supported.
+	/// 
+	/// Besides `color_attachment` the `transfer_destination` flag is almost universally supported.
+	/// The `transfer_source` (for screenshots) and `storage` (for writing from compute shaders) flags are commonly supported on desktop hardware, but are not guaranteed.
+	/// Requesting `transfer_source` disables clipping, see [`SwapchainConfig::clipped`](struct.SwapchainConfig.html#structfield.clipped).
+	pub fn with_usage(
+		context: &Context,
+		device: &Device,
+		window: Arc<Window>,
+		present_mode: PresentMode,
+		depth_format: impl Into<Option<Format>>,
+		inverse_depth: bool,
+		usage: ImageUsage,
+	) -> Result<Swapchain, SwapchainCreationError>
+	{
+		let config = SwapchainConfig { usage, .. SwapchainConfig::default() };
+		Swapchain::create(context, device, window, present_mode, depth_format.into(), inverse_depth, &config, select_srgb_format)
+	}
+
+	/// Create a new Swapchain using provided Device and configuration.
+	/// 
+	/// Same as [`Swapchain::with_usage()`](#method.with_usage), but additionally controls clipping and sharing of the images, see [`SwapchainConfig`](struct.SwapchainConfig.html).
+	/// The configuration is kept when the swapchain is [resized](#method.resize).
+	pub fn with_config(
+		context: &Context,
+		device: &Device,
+		window: Arc<Window>,
+		present_mode: PresentMode,
+		depth_format: impl Into<Option<Format>>,
+		inverse_depth: bool,
+		config: SwapchainConfig,
+	) -> Result<Swapchain, SwapchainCreationError>
+	{
+		Swapchain::create(context, device, window, present_mode, depth_format.into(), inverse_depth, &config, select_srgb_format)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn create(
+		context: &Context,
+		device: &Device,
+		window: Arc<Window>,
+		present_mode: PresentMode,
+		depth_format: Option<Format>,
+		inverse_depth: bool,
+		config: &SwapchainConfig,
+		format_selector: impl Fn(&[ImageFormat]) -> ImageFormat,
 	) -> Result<Swapchain, SwapchainCreationError>
 	{
 		let logical_device = device.logical_device();
 
 		let dimensions: (u32, u32) = window.inner_size().into();
+		if dimensions.0 == 0 || dimensions.1 == 0 { return Err(SwapchainCreationError::UnsizedWindow); }
 		let surface = vulkano_win::create_vk_surface(window, context.instance.clone())?;
-		let (swapchain, images) = create_swapchain(device, surface, dimensions, &device.graphics_queue, present_mode)?;
+		let (swapchain, images, color_space, usage) = create_swapchain(device, surface, dimensions, &device.graphics_queue, present_mode, config, format_selector)?;
 
-		let depths = {
-			let image_count = images.len();
-			let mut images = Vec::with_capacity(image_count);
-			for _ in 0..image_count {
-				images.push(AttachmentImage::transient(logical_device.clone(), [dimensions.0, dimensions.1], depth_format)?);
-			};
-			images
-		};
+		let depths = create_depth_images(&logical_device, depth_format, images.len(), dimensions)?;
 
 		let mut result = Swapchain{
 			device: logical_device,
 			swapchain,
 			images,
 			depths,
+			color_space,
+			usage,
 			depth_format,
-			inverse_depth: false,
+			inverse_depth,
 			dynamic_state: DynamicState::default(),
 			default_viewport: Viewport{ origin: [0f32; 2], dimensions: [0f32; 2], depth_range: 0f32..1f32 },
 		};
 		result.resize_viewport(dimensions);
+		log_event!(info, "gaclen: created swapchain {}x{} with {} images, format {:?} and present mode {:?}", dimensions.0, dimensions.1, result.images.len(), result.swapchain.format(), result.swapchain.present_mode());
 
 		Ok(result)
 	}
@@ -103,17 +270,33 @@ impl Swapchain {
 	/// Forward range is 0.0 being the front and the 1.0 being the away.
 	/// Inverse range is 1.0 the front and 0.0 being the away.
 	/// The advantages of different approaches are to be researched by the reader.
+	/// 
+	/// Inverse range should be paired with [`GraphicalPassBuilder::inverse_depth_test()`](../pass/struct.GraphicalPassBuilder.html#method.inverse_depth_test).
+	/// It is most beneficial with a floating-point depth format (such as `Format::D32Sfloat`), as it evens out the precision across the depth range.
+	/// 
+	/// Prefer setting the range during [creation](#method.new), as changing it recreates the viewport.
 	pub fn inverse_depth(&mut self, inverse: bool) {
 		self.inverse_depth = inverse;
 		let dimensions = {
-			let dimensions = self.depths[0].dimensions();
+			let dimensions = self.swapchain.dimensions();
 			(dimensions[0], dimensions[1])
 		};
 		self.resize_viewport(dimensions);
 	}
 
 	/// Resize the images in the swapchain to provided size.
+	/// 
+	/// The dimensions should be in physical pixels, as returned by `Window::inner_size()`.
+	/// 
+	/// Does not wait for the device to become idle.
+	/// Frames that are still in flight keep the old swapchain and images alive until they are finished, so they are not affected by the recreation.
+	/// Framebuffers built with the old images should be rebuilt.
+	/// Use [`Device::wait_for_idle()`](../device/struct.Device.html#method.wait_for_idle) beforehand to drain in-flight work explicitly.
+	/// 
+	/// Fails with [`ResizeError::Minimized`](../enum.ResizeError.html#variant.Minimized) if either dimension is zero (a minimized window on some platforms), leaving the swapchain unchanged.
+	/// Drawing should be skipped until a successful resize.
 	pub fn resize(&mut self, dimensions: (u32, u32)) -> Result<(), ResizeError> {
+		if dimensions.0 == 0 || dimensions.1 == 0 { return Err(ResizeError::Minimized); }
 		self.resize_viewport(dimensions);
 
 		// TODO: investigate weird UnsupportedDimensions swapchain error on some resizes
@@ -121,14 +304,8 @@ impl Swapchain {
 		self.swapchain = swapchain;
 		self.images = images;
 
-		self.depths = {
-			let image_count = self.images.len();
-			let mut images = Vec::with_capacity(image_count);
-			for _ in 0..image_count {
-				images.push(AttachmentImage::transient(self.device.clone(), [dimensions.0, dimensions.1], self.depth_format)?);
-			};
-			images
-		};
+		self.depths = create_depth_images(&self.device, self.depth_format, self.images.len(), dimensions)?;
+		log_event!(debug, "gaclen: recreated swapchain {}x{}", dimensions.0, dimensions.1);
 
 		Ok(())
 	}
@@ -139,15 +316,97 @@ impl Swapchain {
 	}
 
 	/// Get the target depth image to draw to for provided frame.
-	pub fn get_depth_image_for(&self, frame: &Frame) -> Arc<AttachmentImage> {
-		self.depths[frame.swapchain_index].clone()
+	/// 
+	/// Returns `None` if the swapchain was created without depth.
+	pub fn get_depth_image_for(&self, frame: &Frame) -> Option<Arc<AttachmentImage>> {
+		self.depths.get(frame.swapchain_index).cloned()
 	}
 
+	/// Get all color images of the swapchain, indexed by [`Frame::swapchain_index()`](../frame/struct.Frame.html#method.swapchain_index).
+	/// 
+	/// The images are replaced when the swapchain is [resized](#method.resize).
+	#[inline]
+	pub fn color_images(&self) -> &[Arc<SwapchainImage<Arc<Window>>>] { &self.images }
+
+	/// Get all depth images of the swapchain, indexed by [`Frame::swapchain_index()`](../frame/struct.Frame.html#method.swapchain_index).
+	/// 
+	/// Empty if the swapchain was created without depth.
+	/// The images are replaced when the swapchain is [resized](#method.resize).
+	#[inline]
+	pub fn depth_images(&self) -> &[Arc<AttachmentImage>] { &self.depths }
+
+	/// Build a framebuffer for every image of the swapchain, indexed by [`Frame::swapchain_index()`](../frame/struct.Frame.html#method.swapchain_index).
+	/// 
+	/// The pass should have a color attachment followed by a depth one if the swapchain has depth.
+	/// The framebuffers should be rebuilt after the swapchain is [resized](#method.resize).
+	pub fn build_framebuffers<P>(&self, pass: &GraphicalPass<P>) -> Result<Vec<Arc<dyn FramebufferAbstract + Send + Sync>>, FramebufferBuildError>
+	where
+		P : RenderPassAbstract + Send + Sync + ?Sized + 'static,
+	{
+		let mut framebuffers: Vec<Arc<dyn FramebufferAbstract + Send + Sync>> = Vec::with_capacity(self.images.len());
+		for (index, color) in self.images.iter().enumerate() {
+			let builder = pass.start_framebuffer().add(color.clone())?;
+			match self.depths.get(index) {
+				Some(depth) => framebuffers.push(Arc::new(builder.add(depth.clone())?.build()?)),
+				None => framebuffers.push(Arc::new(builder.build()?)),
+			};
+		};
+		Ok(framebuffers)
+	}
+
+	/// Get the number of images in the swapchain, the maximum number of frames that may be in flight at once.
+	/// 
+	/// May change after [resizing](#method.resize).
+	#[inline]
+	pub fn image_count(&self) -> usize { self.images.len() }
+
+	/// Get the dimensions of the swapchain images.
+	#[inline]
+	pub fn dimensions(&self) -> [u32; 2] { self.swapchain.dimensions() }
+
+	/// Get the format of the swapchain (color) images.
+	#[inline]
+	pub fn format(&self) -> Format { self.swapchain.format() }
+
+	/// Get the color space the images are presented in.
+	#[inline]
+	pub fn color_space(&self) -> ColorSpace { self.color_space }
+
+	/// Get the usage flags of the swapchain (color) images, including the supported [optional](struct.SwapchainConfig.html#structfield.optional_usage) ones.
+	#[inline]
+	pub fn usage(&self) -> ImageUsage { self.usage }
+
+	/// Get the color image of provided frame for sampling in a later pass of the frame, see [sampling the images](index.html#sampling-the-images).
+	/// 
+	/// Returns `None` if the images do not have the `sampled` usage.
+	pub fn sampled_image_for(&self, frame: &Frame) -> Option<Arc<SwapchainImage<Arc<Window>>>> {
+		match self.usage.sampled {
+			true => Some(self.get_color_image_for(frame)),
+			false => None,
+		}
+	}
+
+	/// Get the present mode the swapchain was created with.
+	#[inline]
+	pub fn present_mode(&self) -> PresentMode { self.swapchain.present_mode() }
+
+	/// Get the format of the depth images, `None` if the swapchain was created without depth.
+	pub fn depth_format(&self) -> Option<Format> { self.depth_format }
+
 	/// Get the default viewport for rendering to this swapchain.
 	pub fn default_viewport(&self) -> Viewport {
 		self.default_viewport.clone()
 	}
 
+	/// Get the [vulkano swapchain](struct.Swapchain.html) used for presenting images on the screen.
+	#[cfg(feature = "expose-underlying-vulkano")]
+	#[inline(always)]
+	pub fn swapchain(&self) -> &Arc<VlkSwapchain<Arc<Window>>> { &self.swapchain }
+	/// Get the [vulkano swapchain images](struct.SwapchainImage.html) that are presented on the screen.
+	#[cfg(feature = "expose-underlying-vulkano")]
+	#[inline(always)]
+	pub fn swapchain_images(&self) -> &Vec<Arc<SwapchainImage<Arc<Window>>>> { &self.images }
+
 	fn resize_viewport(&mut self, dimensions: (u32, u32)) {
 		self.default_viewport = {
 			let origin = [0f32; 2];
@@ -170,6 +429,17 @@ impl Swapchain {
 	}
 }
 
+impl Default for SwapchainConfig {
+	fn default() -> Self {
+		Self {
+			usage: ImageUsage::none(),
+			optional_usage: ImageUsage::none(),
+			clipped: None,
+			sharing: ImageSharing::Exclusive,
+		}
+	}
+}
+
 impl From<vulkano::swapchain::SurfaceCreationError> for SwapchainCreationError {
 	fn from(err: vulkano::swapchain::SurfaceCreationError) -> Self { Self::Surface(err) }
 }
@@ -177,21 +447,110 @@ impl From<ImageCreationError> for SwapchainCreationError {
 	fn from(err: ImageCreationError) -> Self { Self::Image(err) }
 }
 
+impl std::fmt::Debug for Swapchain {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		fmt.debug_struct("Swapchain")
+			.field("dimensions", &self.dimensions())
+			.field("format", &self.format())
+			.field("color_space", &self.color_space)
+			.field("present_mode", &self.present_mode())
+			.field("image_count", &self.image_count())
+			.field("depth_format", &self.depth_format)
+			.finish()
+	}
+}
+
+impl std::fmt::Display for SwapchainCreationError {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::Surface(err) => write!(fmt, "failed to create the surface: {}", err),
+			Self::SurfaceCapabilities(err) => write!(fmt, "failed to query the surface capabilities: {}", err),
+			Self::Swapchain(err) => write!(fmt, "failed to create the swapchain: {}", err),
+			Self::Image(err) => write!(fmt, "failed to create the depth images: {}", err),
+			Self::NoCompatibleFormatFound => write!(fmt, "no compatible surface format was found"),
+			Self::UnsizedWindow => write!(fmt, "the window has no size"),
+			Self::UnsupportedUsage(usage) => write!(fmt, "the surface does not support the image usage: {:?}", usage),
+			Self::UnsupportedFormat(format) => write!(fmt, "the surface does not support the format: {:?}", format),
+			Self::NoCompositeAlpha => write!(fmt, "the surface reports no supported composite alpha modes"),
+			Self::UnsupportedPresentMode(mode) => write!(fmt, "the surface does not support the present mode: {:?}", mode),
+		}
+	}
+}
+impl std::error::Error for SwapchainCreationError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Surface(err) => Some(err),
+			Self::SurfaceCapabilities(err) => Some(err),
+			Self::Swapchain(err) => Some(err),
+			Self::Image(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+fn create_depth_images(
+	device: &Arc<LogicalDevice>,
+	format: Option<Format>,
+	count: usize,
+	dimensions: (u32, u32),
+) -> Result<Vec<Arc<AttachmentImage>>, ImageCreationError> {
+	let format = match format {
+		Some(format) => format,
+		None => return Ok(Vec::new()),
+	};
+
+	let mut images = Vec::with_capacity(count);
+	for _ in 0..count {
+		images.push(AttachmentImage::transient(device.clone(), [dimensions.0, dimensions.1], format)?);
+	};
+	Ok(images)
+}
+
+#[allow(clippy::type_complexity)]
 fn create_swapchain(
 	device: &Device,
 	surface: Arc<Surface<Arc<Window>>>,
 	dimensions: (u32, u32),
 	graphics_queue: &Arc<DeviceQueue>,
-	present_mode: PresentMode
-) -> Result<(Arc<VlkSwapchain<Arc<Window>>>, Vec<Arc<SwapchainImage<Arc<Window>>>>), SwapchainCreationError> {
+	present_mode: PresentMode,
+	config: &SwapchainConfig,
+	format_selector: impl Fn(&[ImageFormat]) -> ImageFormat,
+) -> Result<(Arc<VlkSwapchain<Arc<Window>>>, Vec<Arc<SwapchainImage<Arc<Window>>>>, ColorSpace, ImageUsage), SwapchainCreationError> {
 	let capabilities = match surface.capabilities(device.physical_device()) {
 		Ok(caps) => caps,
 		Err(err) => return Err(SwapchainCreationError::SurfaceCapabilities(err)),
 	};
-	let usage = capabilities.supported_usage_flags;
-	let alpha = capabilities.supported_composite_alpha.iter().next().unwrap();
+	let usage = ImageUsage { color_attachment: true, .. config.usage };
+	if let Some(unsupported) = unsupported_usage(usage, capabilities.supported_usage_flags) {
+		return Err(SwapchainCreationError::UnsupportedUsage(unsupported));
+	}
+	let usage = with_supported_usage(usage, config.optional_usage, capabilities.supported_usage_flags);
+	let alpha = match capabilities.supported_composite_alpha.iter().next() {
+		Some(alpha) => alpha,
+		None => return Err(SwapchainCreationError::NoCompositeAlpha),
+	};
+	if !capabilities.present_modes.supports(present_mode) {
+		return Err(SwapchainCreationError::UnsupportedPresentMode(present_mode));
+	}
+	// Some drivers only support the transform the surface currently uses.
+	let transform = match capabilities.supported_transforms.identity {
+		true => vulkano::swapchain::SurfaceTransform::Identity,
+		false => capabilities.current_transform,
+	};
 
-	let (format, color_space) = select_format(capabilities.supported_formats)?;
+	let formats = capabilities.supported_formats;
+	if formats.is_empty() {
+		return Err(SwapchainCreationError::NoCompatibleFormatFound);
+	}
+	let (format, color_space) = format_selector(&formats);
+	if !formats.contains(&(format, color_space)) {
+		return Err(SwapchainCreationError::UnsupportedFormat((format, color_space)));
+	}
+
+	let sharing = match (config.sharing, device.resource_sharing()) {
+		(ImageSharing::Concurrent, FamilySharing::Concurrent(families)) => SharingMode::Concurrent(families),
+		_ => SharingMode::from(graphics_queue),
+	};
 
 	let swapchain = VlkSwapchain::new(
 		device.logical_device(),
@@ -201,35 +560,146 @@ fn create_swapchain(
 		[dimensions.0, dimensions.1],
 		1,
 		usage,
-		graphics_queue,
-		vulkano::swapchain::SurfaceTransform::Identity,
+		sharing,
+		transform,
 		alpha,
 		present_mode,
 		vulkano::swapchain::FullscreenExclusive::Default,
-		true,
+		is_clipped(config, usage),
 		color_space
 	);
 	
 	match swapchain {
-		Ok(swapchain) => Ok(swapchain),
+		Ok((swapchain, images)) => Ok((swapchain, images, color_space, usage)),
 		Err(err) => Err(SwapchainCreationError::Swapchain(err)),
 	}
 }
 
-fn select_format(formats: Vec<ImageFormat>) -> Result<ImageFormat, SwapchainCreationError> {
-	if formats.is_empty() {
-		return Err(SwapchainCreationError::NoCompatibleFormatFound);
-	}
+// Whether the images should be clipped, readback requires all pixels to be defined.
+fn is_clipped(config: &SwapchainConfig, usage: ImageUsage) -> bool {
+	config.clipped.unwrap_or(!usage.transfer_source)
+}
 
-	let mut format = formats[0];
+// Get the requested usage flags that are not supported, if any.
+fn unsupported_usage(requested: ImageUsage, supported: ImageUsage) -> Option<ImageUsage> {
+	let unsupported = ImageUsage {
+		transfer_source: requested.transfer_source && !supported.transfer_source,
+		transfer_destination: requested.transfer_destination && !supported.transfer_destination,
+		sampled: requested.sampled && !supported.sampled,
+		storage: requested.storage && !supported.storage,
+		color_attachment: requested.color_attachment && !supported.color_attachment,
+		depth_stencil_attachment: requested.depth_stencil_attachment && !supported.depth_stencil_attachment,
+		transient_attachment: requested.transient_attachment && !supported.transient_attachment,
+		input_attachment: requested.input_attachment && !supported.input_attachment,
+	};
+	match unsupported == ImageUsage::none() {
+		true => None,
+		false => Some(unsupported),
+	}
+}
 
-	for other in formats {
-		format = choose_better_format(format, other);
+// Add the optional usage flags that are supported to the required ones.
+fn with_supported_usage(required: ImageUsage, optional: ImageUsage, supported: ImageUsage) -> ImageUsage {
+	ImageUsage {
+		transfer_source: required.transfer_source || (optional.transfer_source && supported.transfer_source),
+		transfer_destination: required.transfer_destination || (optional.transfer_destination && supported.transfer_destination),
+		sampled: required.sampled || (optional.sampled && supported.sampled),
+		storage: required.storage || (optional.storage && supported.storage),
+		color_attachment: required.color_attachment || (optional.color_attachment && supported.color_attachment),
+		depth_stencil_attachment: required.depth_stencil_attachment || (optional.depth_stencil_attachment && supported.depth_stencil_attachment),
+		transient_attachment: required.transient_attachment || (optional.transient_attachment && supported.transient_attachment),
+		input_attachment: required.input_attachment || (optional.input_attachment && supported.input_attachment),
 	}
-	Ok(format)
 }
 
-fn choose_better_format(first: ImageFormat, _second: ImageFormat) -> ImageFormat {
-	// TODO: compare and select better format
-	first
+/// Select an *sRGB* format presented in the *sRGB* color space, or the first format if there is none.
+/// 
+/// This is the default format selector for [`Swapchain::new_with()`](struct.Swapchain.html#method.new_with).
+/// 
+/// # Panic.
+/// 
+/// - Panics if `formats` is empty.
+pub fn select_srgb_format(formats: &[ImageFormat]) -> ImageFormat {
+	formats.iter()
+		.cloned()
+		.find(|(format, color_space)| is_srgb(*format) && *color_space == ColorSpace::SrgbNonLinear)
+		.unwrap_or(formats[0])
+}
+
+/// Select an HDR format, or fall back to [`select_srgb_format()`](fn.select_srgb_format.html) if there is none.
+/// 
+/// Prefers *HDR10* (`ColorSpace::Hdr10St2084`), then extended linear *sRGB* (`ColorSpace::ExtendedSrgbLinear`) with a floating-point format.
+/// The shaders are responsible for producing values in the selected color space, see [`Swapchain::color_space()`](struct.Swapchain.html#method.color_space).
+/// 
+/// # Panic.
+/// 
+/// - Panics if `formats` is empty.
+pub fn select_hdr_format(formats: &[ImageFormat]) -> ImageFormat {
+	const PREFERRED: [ImageFormat; 3] = [
+		(Format::A2B10G10R10UnormPack32, ColorSpace::Hdr10St2084),
+		(Format::A2R10G10B10UnormPack32, ColorSpace::Hdr10St2084),
+		(Format::R16G16B16A16Sfloat, ColorSpace::ExtendedSrgbLinear),
+	];
+	PREFERRED.iter()
+		.cloned()
+		.find(|preferred| formats.contains(preferred))
+		.unwrap_or_else(|| select_srgb_format(formats))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_select_srgb_format() {
+		let unorm = (Format::B8G8R8A8Unorm, ColorSpace::SrgbNonLinear);
+		let srgb = (Format::B8G8R8A8Srgb, ColorSpace::SrgbNonLinear);
+		assert_eq!(select_srgb_format(&[unorm, srgb]), srgb);
+		assert_eq!(select_srgb_format(&[unorm]), unorm);
+	}
+
+	#[test]
+	fn test_unsupported_usage() {
+		let supported = ImageUsage { color_attachment: true, transfer_destination: true, .. ImageUsage::none() };
+
+		assert_eq!(unsupported_usage(ImageUsage { color_attachment: true, .. ImageUsage::none() }, supported), None);
+
+		let requested = ImageUsage { color_attachment: true, storage: true, .. ImageUsage::none() };
+		assert_eq!(unsupported_usage(requested, supported), Some(ImageUsage { storage: true, .. ImageUsage::none() }));
+	}
+
+	#[test]
+	fn test_with_supported_usage() {
+		let required = ImageUsage { color_attachment: true, .. ImageUsage::none() };
+		let optional = ImageUsage { sampled: true, storage: true, .. ImageUsage::none() };
+		let supported = ImageUsage { color_attachment: true, sampled: true, .. ImageUsage::none() };
+		assert_eq!(with_supported_usage(required, optional, supported), ImageUsage { color_attachment: true, sampled: true, .. ImageUsage::none() });
+	}
+
+	#[test]
+	fn test_is_clipped() {
+		let color = ImageUsage { color_attachment: true, .. ImageUsage::none() };
+		let transfer_source = ImageUsage { transfer_source: true, .. ImageUsage::none() };
+		let all = ImageUsage { color_attachment: true, transfer_source: true, .. ImageUsage::none() };
+		assert!(is_clipped(&SwapchainConfig::default(), color));
+
+		let readback = SwapchainConfig { usage: transfer_source, .. SwapchainConfig::default() };
+		assert!(!is_clipped(&readback, all));
+		assert!(is_clipped(&SwapchainConfig { clipped: Some(true), .. readback }, all));
+
+		// optional usage counts if the surface supports it
+		let optional_readback = SwapchainConfig { optional_usage: transfer_source, .. SwapchainConfig::default() };
+		assert!(!is_clipped(&optional_readback, with_supported_usage(color, transfer_source, all)));
+		assert!(is_clipped(&optional_readback, with_supported_usage(color, transfer_source, color)));
+	}
+
+	#[test]
+	fn test_select_hdr_format() {
+		let srgb = (Format::B8G8R8A8Srgb, ColorSpace::SrgbNonLinear);
+		let linear = (Format::R16G16B16A16Sfloat, ColorSpace::ExtendedSrgbLinear);
+		let hdr10 = (Format::A2B10G10R10UnormPack32, ColorSpace::Hdr10St2084);
+		assert_eq!(select_hdr_format(&[srgb, linear, hdr10]), hdr10);
+		assert_eq!(select_hdr_format(&[srgb, linear]), linear);
+		assert_eq!(select_hdr_format(&[srgb]), srgb);
+	}
 }
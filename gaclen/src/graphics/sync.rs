@@ -0,0 +1,105 @@
+//! Sync provides explicit synchronization for work submitted outside of [`Frame`](../frame/struct.Frame.html).
+//!
+//! Frames synchronize themselves, explicit synchronization is only required for custom work, such as async compute overlapping drawing or streaming uploads.
+//! Work is [submitted](fn.submit.html) to one of the queues of a device and then signals either:
+//! - a [`Semaphore`](struct.Semaphore.html), that other GPU work (including [the next frame](fn.wait_before_next_frame.html)) can wait on, or
+//! - a [`Fence`](struct.Fence.html), that the CPU can wait on.
+//!
+//! ```ignore
+//! let simulated = gaclen::graphics::sync::submit(&device, QueueOperation::Compute, Vec::new(), |commands| commands.dispatch(..).unwrap())?
+//! 	.signal_semaphore()?;
+//! // The next frame starts drawing once the simulation is finished, without blocking the CPU.
+//! gaclen::graphics::sync::wait_before_next_frame(&mut device, simulated);
+//! ```
+
+use super::device::{Device, QueueOperation, SubmitError};
+
+use std::time::Duration;
+
+use vulkano::command_buffer::AutoCommandBufferBuilder;
+use vulkano::sync::{FenceSignalFuture, FlushError, GpuFuture};
+
+/// Submitted GPU work that has not yet signaled anything.
+///
+/// Should be finished by signaling a [`Semaphore`](struct.Semaphore.html) or a [`Fence`](struct.Fence.html), which also flushes the work to the GPU.
+#[must_use = "the work is only flushed to the GPU once it signals a semaphore or a fence"]
+pub struct Submission {
+	future: Box<dyn GpuFuture>,
+}
+
+/// A signal from GPU work to other GPU work, possibly on a different queue.
+///
+/// Created by [`Submission::signal_semaphore()`](struct.Submission.html#method.signal_semaphore), consumed by the work that waits on it.
+pub struct Semaphore {
+	future: Box<dyn GpuFuture>,
+}
+
+/// A signal from GPU work to the CPU.
+///
+/// Created by [`Submission::signal_fence()`](struct.Submission.html#method.signal_fence).
+/// Dropping a fence blocks until the work is finished.
+pub struct Fence {
+	future: FenceSignalFuture<Box<dyn GpuFuture>>,
+}
+
+/// Record and submit commands to the queue used for given `operation`, once all `wait` semaphores are signaled.
+///
+/// Semaphores signaled by work on other queues are waited on by the GPU, the CPU is not blocked.
+///
+/// # Panic.
+///
+/// - Panics if fails to create the command buffer.
+/// - Panics if fails to build (finalize) the command buffer.
+pub fn submit<F>(device: &Device, operation: QueueOperation, wait: Vec<Semaphore>, record: F) -> Result<Submission, SubmitError>
+where
+	F : FnOnce(AutoCommandBufferBuilder) -> AutoCommandBufferBuilder,
+{
+	let before = wait.into_iter().fold(Box::new(vulkano::sync::now(device.logical_device())) as Box<dyn GpuFuture>, |before, semaphore| Box::new(before.join(semaphore.future)));
+	let future = device.submit_after(before, operation, record)?;
+	Ok(Submission { future: Box::new(future) })
+}
+
+/// Make the next [`Frame`](../frame/struct.Frame.html) begun on the device wait for the semaphore before drawing.
+pub fn wait_before_next_frame(device: &mut Device, semaphore: Semaphore) {
+	let time: Box<dyn GpuFuture> = match device.before_frame.take() {
+		Some(time) => Box::new(time.join(semaphore.future)),
+		None => semaphore.future,
+	};
+	device.before_frame = Some(time);
+}
+
+impl Submission {
+	/// Flush the work to the GPU and signal a semaphore once it is finished.
+	pub fn signal_semaphore(self) -> Result<Semaphore, FlushError> {
+		let future = self.future.then_signal_semaphore_and_flush()?;
+		Ok(Semaphore { future: Box::new(future) })
+	}
+
+	/// Flush the work to the GPU and signal a fence once it is finished.
+	pub fn signal_fence(self) -> Result<Fence, FlushError> {
+		let future = self.future.then_signal_fence_and_flush()?;
+		Ok(Fence { future })
+	}
+}
+
+impl Fence {
+	/// Check whether the work is finished without blocking.
+	#[inline]
+	pub fn is_signaled(&self) -> Result<bool, FlushError> { self.future.is_signaled() }
+
+	/// Block until the work is finished or the `timeout` passes.
+	///
+	/// Waits indefinitely if `timeout` is `None`.
+	#[inline]
+	pub fn wait(&self, timeout: Option<Duration>) -> Result<(), FlushError> { self.future.wait(timeout) }
+}
+
+impl std::fmt::Debug for Submission {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result { fmt.write_str("Submission") }
+}
+impl std::fmt::Debug for Semaphore {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result { fmt.write_str("Semaphore") }
+}
+impl std::fmt::Debug for Fence {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result { fmt.write_str("Fence") }
+}
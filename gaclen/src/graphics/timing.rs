@@ -0,0 +1,113 @@
+//! Timing provides utilities for measuring frame times.
+//!
+//! A [`FrameClock`](struct.FrameClock.html) should be [ticked](struct.FrameClock.html#method.tick) once per drawn frame:
+//! ```
+//! let mut clock = gaclen::graphics::timing::FrameClock::new();
+//! // In the main loop:
+//! clock.tick();
+//! let delta = clock.delta_seconds(); // use for animation
+//! ```
+
+use std::time::{Duration, Instant};
+
+// Weight of the latest frame in the smoothed frame time.
+const SMOOTHING_FACTOR: f64 = 0.1;
+
+/// A clock tracking the time between frames.
+#[derive(Clone, Debug)]
+pub struct FrameClock {
+	start: Instant,
+	last_tick: Instant,
+	delta: Duration,
+	smoothed_delta: f64,
+	frame_count: u64,
+}
+
+impl FrameClock {
+	/// Create a new clock, starting now.
+	pub fn new() -> Self {
+		let now = Instant::now();
+		Self {
+			start: now,
+			last_tick: now,
+			delta: Duration::default(),
+			smoothed_delta: 0.0,
+			frame_count: 0,
+		}
+	}
+
+	/// Mark the end of a frame, should be called once per frame.
+	///
+	/// Returns the time since the previous tick.
+	pub fn tick(&mut self) -> Duration {
+		let now = Instant::now();
+		self.delta = now - self.last_tick;
+		self.last_tick = now;
+
+		let delta = self.delta.as_secs_f64();
+		self.smoothed_delta = match self.frame_count {
+			0 => delta,
+			_ => self.smoothed_delta + (delta - self.smoothed_delta) * SMOOTHING_FACTOR,
+		};
+		self.frame_count += 1;
+
+		self.delta
+	}
+
+	/// Get the time between the two latest ticks.
+	#[inline]
+	pub fn delta(&self) -> Duration { self.delta }
+
+	/// Get the time between the two latest ticks in seconds, useful for animation.
+	#[inline]
+	pub fn delta_seconds(&self) -> f32 { self.delta.as_secs_f32() }
+
+	/// Get the time since the clock was created.
+	#[inline]
+	pub fn elapsed(&self) -> Duration { self.start.elapsed() }
+
+	/// Get the total number of ticks (frames).
+	#[inline]
+	pub fn total_frames(&self) -> u64 { self.frame_count }
+
+	/// Get the average number of frames per second since the clock was created.
+	pub fn average_fps(&self) -> f64 {
+		let elapsed = self.elapsed().as_secs_f64();
+		match elapsed > 0.0 {
+			true => self.frame_count as f64 / elapsed,
+			false => 0.0,
+		}
+	}
+
+	/// Get the number of frames per second, smoothed over recent frames.
+	pub fn smoothed_fps(&self) -> f64 {
+		match self.smoothed_delta > 0.0 {
+			true => 1.0 / self.smoothed_delta,
+			false => 0.0,
+		}
+	}
+}
+
+impl Default for FrameClock {
+	fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_tick() {
+		let mut clock = FrameClock::new();
+		assert_eq!(clock.total_frames(), 0);
+		assert_eq!(clock.smoothed_fps(), 0.0);
+
+		std::thread::sleep(Duration::from_millis(5));
+		let delta = clock.tick();
+		assert_eq!(clock.total_frames(), 1);
+		assert!(delta >= Duration::from_millis(5));
+		assert_eq!(clock.delta(), delta);
+		assert!(clock.smoothed_fps() > 0.0);
+		assert!(clock.average_fps() > 0.0);
+	}
+}
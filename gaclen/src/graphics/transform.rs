@@ -0,0 +1,137 @@
+//! Transform describes the placement of an object in the world.
+//!
+//! A [`Transform`](struct.Transform.html) is a composition of scaling, rotation and translation (applied in that order).
+
+use cgmath::{ElementWise, InnerSpace, Matrix4, One, Quaternion, Vector3, Zero};
+
+/// Placement of an object in the world: its scaling, rotation and position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+	/// Translation of the object.
+	pub position: Vector3<f32>,
+	/// Rotation of the object.
+	pub rotation: Quaternion<f32>,
+	/// Scaling of the object along each axis.
+	pub scaling: Vector3<f32>,
+}
+
+impl Transform {
+	/// Create a new transform from its components.
+	#[inline]
+	pub fn new(position: Vector3<f32>, rotation: Quaternion<f32>, scaling: Vector3<f32>) -> Self {
+		Self { position, rotation, scaling }
+	}
+
+	/// Create a transform that only translates.
+	#[inline]
+	pub fn from_position(position: Vector3<f32>) -> Self { Self { position, .. Self::default() } }
+
+	/// Create a transform that only rotates.
+	#[inline]
+	pub fn from_rotation(rotation: Quaternion<f32>) -> Self { Self { rotation, .. Self::default() } }
+
+	/// Create a transform that only scales.
+	#[inline]
+	pub fn from_scaling(scaling: Vector3<f32>) -> Self { Self { scaling, .. Self::default() } }
+
+	/// Compute the matrix that applies this transform.
+	pub fn compute_matrix(&self) -> Matrix4<f32> {
+		Matrix4::from_translation(self.position)
+			* Matrix4::from(self.rotation)
+			* Matrix4::from_nonuniform_scale(self.scaling.x, self.scaling.y, self.scaling.z)
+	}
+
+	/// Compute the matrix that undoes this transform.
+	///
+	/// Unlike [`inverse()`](#method.inverse) is exact for non-uniform scaling.
+	pub fn compute_inverse_matrix(&self) -> Matrix4<f32> {
+		Matrix4::from_nonuniform_scale(1.0 / self.scaling.x, 1.0 / self.scaling.y, 1.0 / self.scaling.z)
+			* Matrix4::from(self.rotation.conjugate())
+			* Matrix4::from_translation(-self.position)
+	}
+
+	/// Get the transform that undoes this one.
+	///
+	/// Useful for finding view matrices from camera transforms.
+	/// The result is exact only for uniform scaling, use [`compute_inverse_matrix()`](#method.compute_inverse_matrix) otherwise.
+	pub fn inverse(&self) -> Self {
+		let rotation = self.rotation.conjugate();
+		let scaling = Vector3 { x: 1.0 / self.scaling.x, y: 1.0 / self.scaling.y, z: 1.0 / self.scaling.z };
+		let position = (rotation * -self.position).mul_element_wise(scaling);
+		Self { position, rotation, scaling }
+	}
+
+	/// Compose this transform with a child one, producing a transform that applies the child first and then this one.
+	///
+	/// The result is exact only if this transform has uniform scaling.
+	pub fn then(&self, child: &Transform) -> Self {
+		Self {
+			position: self.position + self.rotation * child.position.mul_element_wise(self.scaling),
+			rotation: (self.rotation * child.rotation).normalize(),
+			scaling: self.scaling.mul_element_wise(child.scaling),
+		}
+	}
+}
+
+impl Default for Transform {
+	fn default() -> Self {
+		Self {
+			position: Vector3::zero(),
+			rotation: Quaternion::one(),
+			scaling: Vector3 { x: 1.0, y: 1.0, z: 1.0 },
+		}
+	}
+}
+
+impl From<Transform> for Matrix4<f32> {
+	fn from(transform: Transform) -> Self { transform.compute_matrix() }
+}
+
+impl From<Transform> for [[f32; 4]; 4] {
+	fn from(transform: Transform) -> Self { transform.compute_matrix().into() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cgmath::{Deg, Rotation3, SquareMatrix};
+
+	fn assert_matrix_eq(left: Matrix4<f32>, right: Matrix4<f32>) {
+		let left: [[f32; 4]; 4] = left.into();
+		let right: [[f32; 4]; 4] = right.into();
+		for (left, right) in left.iter().flatten().zip(right.iter().flatten()) {
+			assert!((left - right).abs() < 1e-5, "{:?} != {:?}", left, right);
+		}
+	}
+
+	fn sample() -> Transform {
+		Transform::new(
+			Vector3 { x: 1.0, y: -2.0, z: 3.0 },
+			Quaternion::from_angle_z(Deg(30.0)),
+			Vector3 { x: 2.0, y: 2.0, z: 2.0 },
+		)
+	}
+
+	#[test]
+	fn test_default_is_identity() {
+		assert_matrix_eq(Transform::default().compute_matrix(), Matrix4::identity());
+	}
+
+	#[test]
+	fn test_inverse() {
+		let transform = sample();
+		assert_matrix_eq(transform.compute_matrix() * transform.inverse().compute_matrix(), Matrix4::identity());
+		assert_matrix_eq(transform.compute_matrix() * transform.compute_inverse_matrix(), Matrix4::identity());
+	}
+
+	#[test]
+	fn test_then() {
+		let parent = sample();
+		let child = Transform::new(
+			Vector3 { x: 0.5, y: 0.0, z: -1.0 },
+			Quaternion::from_angle_x(Deg(45.0)),
+			Vector3 { x: 1.0, y: 3.0, z: 1.0 },
+		);
+		assert_matrix_eq(parent.then(&child).compute_matrix(), parent.compute_matrix() * child.compute_matrix());
+	}
+}
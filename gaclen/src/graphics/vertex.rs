@@ -0,0 +1,32 @@
+//! Vertex provides standard vertex layouts.
+//!
+//! The layouts can be used as input to a [`GraphicalPass`](../pass/struct.GraphicalPass.html) directly:
+//! ```
+//! let pass = gaclen::graphics::pass::GraphicalPass::start()
+//! 	.single_buffer_input::<gaclen::graphics::vertex::Vertex3D>();
+//! ```
+//!
+//! The shader inputs should use the same names as the fields.
+//! Custom layouts are declared with [`impl_vertex!`](../macro.impl_vertex.html).
+
+/// Vertex of 3D geometry, used by the [primitives](../geometry/index.html).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vertex3D {
+	/// Position of the vertex in object-space.
+	pub position: [f32; 3],
+	/// Normal of the surface at the vertex.
+	pub normal: [f32; 3],
+	/// Texture coordinates of the vertex.
+	pub uv: [f32; 2],
+}
+vulkano::impl_vertex!(Vertex3D, position, normal, uv);
+
+/// Vertex of 2D geometry, such as sprites and interfaces.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vertex2D {
+	/// Position of the vertex.
+	pub position: [f32; 2],
+	/// Texture coordinates of the vertex.
+	pub uv: [f32; 2],
+}
+vulkano::impl_vertex!(Vertex2D, position, uv);
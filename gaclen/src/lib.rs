@@ -10,7 +10,22 @@
 //! Notes:
 //! - The library is in active development and has limited functionality at the moment.
 //! - Members exposes with 'expose-underlying-vulkano' feature use [nightly documentation](https://github.com/rust-lang/rust/issues/43466). The links will be broken.
+//! - The 'strict-validation' feature enables additional runtime checks that catch misuse during development, such as dropping a [`Device`](graphics/device/struct.Device.html) with GPU work in flight. It enables the 'logging' feature to report the misuse.
+//! - The 'logging' feature reports lifecycle events (selected device, swapchain (re)creation, presentation failures) through the [log](https://docs.rs/log/) facade.
 //! - The examples use sister-project: [gaclen_shader](https://crates.io/crates/gaclen_shader).
 
 pub use winit;
+pub use cgmath;
+
+// Log through the `log` facade if the 'logging' feature is enabled, otherwise the arguments are not evaluated (but still count as used).
+macro_rules! log_event {
+	($level:ident, $($arg:tt)+) => {
+		#[cfg(feature = "logging")]
+		{ log::$level!($($arg)+); }
+		#[cfg(not(feature = "logging"))]
+		{ if false { let _ = format_args!($($arg)+); } }
+	};
+}
+
 pub mod graphics;
+pub mod window;
@@ -0,0 +1,135 @@
+//! Window provides helpers for OS window integration on top of [winit](https://docs.rs/winit/).
+//! 
+//! The helpers are intentionally thin, the [`Window`](struct.Window.html) itself is still created and used through winit.
+//! 
+//! # DPI
+//! 
+//! Winit distinguishes between *physical* (actual pixels) and *logical* (scaled by the OS) coordinates.
+//! Rendering (including [`Swapchain`](../graphics/swapchain/struct.Swapchain.html) dimensions) should always use physical pixels, as returned by `Window::inner_size()`.
+//! Logical coordinates are useful for UI layout, use [`to_physical_position()`](fn.to_physical_position.html) and [`to_logical_position()`](fn.to_logical_position.html) to convert between the two.
+//! 
+//! # Input
+//! 
+//! The [`input`](input/index.html) module provides an optional [`InputState`](input/struct.InputState.html) that tracks pressed keys and mouse movement from winit events.
+
+pub mod input;
+
+pub use winit::window::{Fullscreen, Window, WindowBuilder};
+pub use winit::monitor::{MonitorHandle, VideoMode};
+pub use winit::error::ExternalError;
+
+pub use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+
+/// The state of the cursor after [`set_cursor_captured()`](fn.set_cursor_captured.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CursorCapture {
+	/// The cursor is visible and free to move.
+	Released,
+	/// The cursor is hidden and grabbed by the OS.
+	Grabbed,
+	/// The cursor is hidden, but the OS refused to grab it.
+	/// 
+	/// The cursor should be recentered using [`center_cursor()`](fn.center_cursor.html) after processing every move.
+	Recentering,
+}
+
+/// Toggle the window between windowed and borderless fullscreen modes.
+/// 
+/// Uses provided monitor or the one the window is currently on if none is provided.
+pub fn toggle_fullscreen(window: &Window, monitor: Option<MonitorHandle>) {
+	match window.fullscreen() {
+		Some(_) => set_windowed(window),
+		None => window.set_fullscreen(Some(Fullscreen::Borderless(select_monitor(window, monitor)))),
+	}
+}
+
+/// Set the window to be borderless fullscreen on the monitor it is currently on.
+pub fn set_borderless_fullscreen(window: &Window) {
+	window.set_fullscreen(Some(Fullscreen::Borderless(window.current_monitor())));
+}
+
+/// Set the window to be exclusive fullscreen using the best video mode of a monitor.
+/// 
+/// Uses provided monitor or the one the window is currently on if none is provided.
+/// Falls back to borderless fullscreen if the monitor reports no video modes.
+pub fn set_exclusive_fullscreen(window: &Window, monitor: Option<MonitorHandle>) {
+	let monitor = select_monitor(window, monitor);
+	match best_video_mode(&monitor) {
+		Some(mode) => window.set_fullscreen(Some(Fullscreen::Exclusive(mode))),
+		None => window.set_fullscreen(Some(Fullscreen::Borderless(monitor))),
+	}
+}
+
+/// Set the window to be windowed (not fullscreen).
+pub fn set_windowed(window: &Window) {
+	window.set_fullscreen(None);
+}
+
+/// Get the best video mode of a monitor.
+/// 
+/// Prefers larger resolutions, then higher bit depths and then higher refresh rates.
+pub fn best_video_mode(monitor: &MonitorHandle) -> Option<VideoMode> {
+	monitor.video_modes().max_by_key(|mode| {
+		let size = mode.size();
+		(size.width as u64 * size.height as u64, mode.bit_depth(), mode.refresh_rate())
+	})
+}
+
+/// Capture (hide and grab) or release the cursor, useful for relative mouse look.
+/// 
+/// Falls back to [`CursorCapture::Recentering`](enum.CursorCapture.html#variant.Recentering) if the OS refuses to grab the cursor.
+/// Some platforms (notably X11 right after a focus change) refuse grabbing the cursor until the window is focused,
+/// in that case call this again after receiving `WindowEvent::Focused(true)`.
+pub fn set_cursor_captured(window: &Window, captured: bool) -> CursorCapture {
+	window.set_cursor_visible(!captured);
+
+	if !captured {
+		// Releasing a cursor that was never grabbed may fail, which is fine.
+		let _ = window.set_cursor_grab(false);
+		return CursorCapture::Released;
+	}
+
+	if window.set_cursor_grab(true).is_ok() { return CursorCapture::Grabbed; }
+
+	let _ = center_cursor(window);
+	CursorCapture::Recentering
+}
+
+/// Move the cursor to the center of the window.
+/// 
+/// Useful for implementing relative mouse look when the cursor can not be grabbed.
+pub fn center_cursor(window: &Window) -> Result<(), ExternalError> {
+	let size = window.inner_size();
+	window.set_cursor_position(PhysicalPosition::new(size.width as f64 / 2.0, size.height as f64 / 2.0))
+}
+
+/// Get the scale factor of the window, the ratio of physical pixels to logical ones.
+#[inline]
+pub fn scale_factor(window: &Window) -> f64 { window.scale_factor() }
+
+/// Convert a logical position within the window to a physical one (in rendered pixels).
+#[inline]
+pub fn to_physical_position(window: &Window, position: LogicalPosition<f64>) -> PhysicalPosition<f64> {
+	position.to_physical(window.scale_factor())
+}
+
+/// Convert a physical position within the window (in rendered pixels) to a logical one.
+#[inline]
+pub fn to_logical_position(window: &Window, position: PhysicalPosition<f64>) -> LogicalPosition<f64> {
+	position.to_logical(window.scale_factor())
+}
+
+/// Get the size of the window in physical pixels, the size the swapchain should use.
+#[inline]
+pub fn physical_size(window: &Window) -> PhysicalSize<u32> { window.inner_size() }
+
+/// Get the size of the window in logical units.
+#[inline]
+pub fn logical_size(window: &Window) -> LogicalSize<f64> { window.inner_size().to_logical(window.scale_factor()) }
+
+fn select_monitor(window: &Window, monitor: Option<MonitorHandle>) -> MonitorHandle {
+	match monitor {
+		Some(monitor) => monitor,
+		None => window.current_monitor(),
+	}
+}
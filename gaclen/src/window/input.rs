@@ -0,0 +1,110 @@
+//! Input tracks the state of keyboard and mouse from winit events.
+//! 
+//! An [`InputState`](struct.InputState.html) should be fed every event and [reset](struct.InputState.html#method.end_frame) after every frame:
+//! ```ignore
+//! let mut input = gaclen::window::input::InputState::new();
+//! event_loop.run(move |event, _, control_flow| {
+//! 	input.process_event(&event);
+//! 	match event {
+//! 		Event::RedrawEventsCleared => {
+//! 			if input.is_key_down(VirtualKeyCode::W) { /* move forward */ };
+//! 			input.end_frame();
+//! 		},
+//! 		_ => (),
+//! 	}
+//! });
+//! ```
+//! 
+//! The events are only observed, so they can still be handled directly for anything the state does not cover.
+
+use winit::dpi::PhysicalPosition;
+use winit::event::{DeviceEvent, ElementState, Event, KeyboardInput, WindowEvent};
+
+use std::collections::HashSet;
+
+pub use winit::event::{MouseButton, VirtualKeyCode};
+
+/// Current state of the keyboard and mouse.
+#[derive(Clone, Debug, Default)]
+pub struct InputState {
+	keys_down: HashSet<VirtualKeyCode>,
+	buttons_down: HashSet<MouseButton>,
+	cursor_position: Option<PhysicalPosition<f64>>,
+	cursor_delta: (f64, f64),
+	mouse_delta: (f64, f64),
+}
+
+impl InputState {
+	/// Create a new state with nothing pressed.
+	pub fn new() -> Self { Self::default() }
+
+	/// Update the state using a winit event.
+	/// 
+	/// Releases everything if the window loses focus, since the release events are not received in that case.
+	pub fn process_event<T>(&mut self, event: &Event<T>) {
+		match event {
+			Event::WindowEvent { event, .. } => match event {
+				WindowEvent::KeyboardInput { input: KeyboardInput { state, virtual_keycode: Some(key), .. }, .. } => {
+					match state {
+						ElementState::Pressed => self.keys_down.insert(*key),
+						ElementState::Released => self.keys_down.remove(key),
+					};
+				},
+				WindowEvent::MouseInput { state, button, .. } => {
+					match state {
+						ElementState::Pressed => self.buttons_down.insert(*button),
+						ElementState::Released => self.buttons_down.remove(button),
+					};
+				},
+				WindowEvent::CursorMoved { position, .. } => {
+					if let Some(last) = self.cursor_position {
+						self.cursor_delta.0 += position.x - last.x;
+						self.cursor_delta.1 += position.y - last.y;
+					};
+					self.cursor_position = Some(*position);
+				},
+				WindowEvent::CursorLeft { .. } => self.cursor_position = None,
+				WindowEvent::Focused(false) => {
+					self.keys_down.clear();
+					self.buttons_down.clear();
+				},
+				_ => (),
+			},
+			Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } => {
+				self.mouse_delta.0 += delta.0;
+				self.mouse_delta.1 += delta.1;
+			},
+			_ => (),
+		}
+	}
+
+	/// Reset the per-frame state, should be called once the frame has been processed.
+	pub fn end_frame(&mut self) {
+		self.cursor_delta = (0.0, 0.0);
+		self.mouse_delta = (0.0, 0.0);
+	}
+
+	/// Check whether a key is currently held down.
+	#[inline]
+	pub fn is_key_down(&self, key: VirtualKeyCode) -> bool { self.keys_down.contains(&key) }
+
+	/// Check whether a mouse button is currently held down.
+	#[inline]
+	pub fn is_mouse_button_down(&self, button: MouseButton) -> bool { self.buttons_down.contains(&button) }
+
+	/// Get the position of the cursor within the window in physical pixels, `None` if the cursor is outside the window.
+	#[inline]
+	pub fn cursor_position(&self) -> Option<PhysicalPosition<f64>> { self.cursor_position }
+
+	/// Get the movement of the cursor within the window (in physical pixels) during the current frame.
+	/// 
+	/// The cursor does not move while it is [grabbed](../fn.set_cursor_captured.html), use [`mouse_delta()`](#method.mouse_delta) for relative mouse look instead.
+	#[inline]
+	pub fn cursor_delta(&self) -> (f64, f64) { self.cursor_delta }
+
+	/// Get the raw movement of the mouse during the current frame.
+	/// 
+	/// The units are platform-specific (typically unaccelerated pixels), the movement is reported even if the cursor is grabbed.
+	#[inline]
+	pub fn mouse_delta(&self) -> (f64, f64) { self.mouse_delta }
+}
@@ -0,0 +1,47 @@
+//! Dispatch tests for `ComputePass`.
+//!
+//! The tests require a Vulkan capable device, so they are ignored by default.
+//! Run them with `cargo test -- --ignored` on a machine with one.
+
+// Allow `shader!` macro to use this project's gaclen dependency.
+extern crate gaclen;
+
+use gaclen::graphics::prelude::*;
+use gaclen::graphics::vulkano::sync::GpuFuture;
+
+mod doubler {
+	gaclen_shader::shader!{
+		ty: "compute",
+		src: "
+			#version 450
+			layout(local_size_x = 64) in;
+			layout(set = 0, binding = 0) buffer Data { uint values[]; } data;
+			void main() {
+				data.values[gl_GlobalInvocationID.x] *= 2;
+			}
+		",
+	}
+}
+
+#[test]
+#[ignore] // requires a Vulkan capable device
+fn test_dispatch_storage_buffer() {
+	let context = Context::new().unwrap();
+	let device = Device::new_headless(&context).unwrap();
+
+	let shader = doubler::Shader::load(&device).unwrap();
+	let pass = ComputePass::new(&device, &shader.main_entry_point(), &()).unwrap();
+
+	let usage = BufferUsage { storage_buffer: true, .. BufferUsage::none() };
+	let buffer = device.create_array_buffer(0..256u32, usage).unwrap();
+	let descriptor_set = pass.start_persistent_descriptor_set(0)
+		.add_buffer(buffer.clone()).unwrap()
+		.build().unwrap();
+
+	pass.dispatch(&device, [256 / 64, 1, 1], descriptor_set, ()).unwrap()
+		.then_signal_fence_and_flush().unwrap()
+		.wait(None).unwrap();
+
+	let values = buffer.read().unwrap();
+	assert!(values.iter().enumerate().all(|(i, &value)| value == 2 * i as u32));
+}
@@ -143,7 +143,7 @@ pub fn compile(path: Option<String>, base_path: &impl AsRef<Path>, code: &str, t
     Ok(content)
 }
 
-pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Error> {
+pub fn reflect(name: &str, spirv: &[u32], push_constants: Option<&syn::Type>, dump: bool) -> Result<TokenStream, Error> {
     let struct_name = Ident::new(&name, Span::call_site());
     let doc = parse::parse_spirv(spirv)?;
 
@@ -200,6 +200,17 @@ pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Err
     let structs = structs::write_structs(&doc);
     let descriptor_sets = descriptor_sets::write_descriptor_sets(&doc);
     let specialization_constants = spec_consts::write_specialization_constants(&doc);
+
+    // the size of the shared push constants type is checked at compile-time
+    let push_constants_size = descriptor_sets::push_constants_size(&doc);
+    let shared_push_constants = match push_constants {
+        Some(ty) => quote!{
+            /// Push constants type shared with other shaders, its size is checked to match the one used by this shader.
+            pub type PushConstants = #ty;
+            const _: [(); #push_constants_size] = [(); ::std::mem::size_of::<PushConstants>()];
+        },
+        None => TokenStream::new(),
+    };
     let ast = quote!{
         #[allow(unused_imports)]
         use std::sync::Arc;
@@ -276,6 +287,11 @@ pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Err
             #structs
         }
 
+        /// Size of the push constants used by the shader in bytes, `0` if there are none.
+        #[allow(dead_code)]
+        pub const PUSH_CONSTANTS_SIZE: usize = #push_constants_size;
+        #shared_push_constants
+
         #descriptor_sets
         #specialization_constants
     };
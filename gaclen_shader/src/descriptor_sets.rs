@@ -15,6 +15,24 @@ use crate::enums::{Dim, Decoration, StorageClass, ImageFormat};
 use crate::parse::{Instruction, Spirv};
 use crate::spirv_search;
 
+/// Returns the size in bytes of the push constants used by the shader, `0` if there are none.
+pub fn push_constants_size(doc: &Spirv) -> usize {
+    // Looping to find all the push constant structs.
+    let mut push_constants_size = 0;
+    for instruction in doc.instructions.iter() {
+        let type_id = match instruction {
+            &Instruction::TypePointer { type_id, storage_class: StorageClass::StorageClassPushConstant, .. }
+              => type_id,
+            _ => continue,
+        };
+
+        let (_, size, _) = crate::structs::type_from_id(doc, type_id);
+        let size = size.expect("Found runtime-sized push constants");
+        push_constants_size = cmp::max(push_constants_size, size);
+    }
+    push_constants_size
+}
+
 pub fn write_descriptor_sets(doc: &Spirv) -> TokenStream {
     // TODO: not implemented correctly
 
@@ -52,19 +70,7 @@ pub fn write_descriptor_sets(doc: &Spirv) -> TokenStream {
         descriptors.push(Descriptor { desc_ty, set, binding, array_count, readonly });
     }
 
-    // Looping to find all the push constant structs.
-    let mut push_constants_size = 0;
-    for instruction in doc.instructions.iter() {
-        let type_id = match instruction {
-            &Instruction::TypePointer { type_id, storage_class: StorageClass::StorageClassPushConstant, .. }
-              => type_id,
-            _ => continue,
-        };
-
-        let (_, size, _) = crate::structs::type_from_id(doc, type_id);
-        let size = size.expect("Found runtime-sized push constants");
-        push_constants_size = cmp::max(push_constants_size, size);
-    }
+    let push_constants_size = push_constants_size(doc);
 
     // Writing the body of the `descriptor` method.
     let descriptor_body = descriptors
@@ -7,6 +7,19 @@
 //! ```
 //! 
 //! The changes include tweaks to the generated code to use gaclen::vulkano to avoid the necessity of including vulkano in gaclen-dependent projects.
+//! 
+//! The generated module contains `PUSH_CONSTANTS_SIZE`, the size of the push constants the shader uses.
+//! A push constants type shared between shaders can be provided with the `push_constants` field, relative to the module the macro is invoked in.
+//! It is exposed as `PushConstants` and compilation fails if its size does not match the one used by the shader:
+//! ```ignore
+//! mod vertex {
+//!     gaclen_shader::shader!{
+//!         ty: "vertex",
+//!         path: "shader.vert",
+//!         push_constants: "super::CameraPushConstants",
+//!     }
+//! }
+//! ```
 
 #![recursion_limit = "1024"]
 #[macro_use]
@@ -44,6 +57,7 @@ struct MacroInput {
     source_kind: SourceKind,
     include_directories: Vec<String>,
     macro_defines: Vec<(String, String)>,
+    push_constants: Option<syn::Type>,
     dump: bool,
 }
 
@@ -54,6 +68,7 @@ impl Parse for MacroInput {
         let mut source_kind = None;
         let mut include_directories = Vec::new();
         let mut macro_defines = Vec::new();
+        let mut push_constants = None;
 
         while !input.is_empty() {
             let name: Ident = input.parse()?;
@@ -125,6 +140,13 @@ impl Parse for MacroInput {
                         }
                     }
                 }
+                "push_constants" => {
+                    if push_constants.is_some() {
+                        panic!("Only one `push_constants` can be defined")
+                    }
+                    let ty: LitStr = input.parse()?;
+                    push_constants = Some(ty.parse::<syn::Type>()?);
+                }
                 "dump" => {
                     if dump.is_some() {
                         panic!("Only one `dump` can be defined")
@@ -152,7 +174,7 @@ impl Parse for MacroInput {
 
         let dump = dump.unwrap_or(false);
 
-        Ok(MacroInput { shader_kind, source_kind, include_directories, dump, macro_defines })
+        Ok(MacroInput { shader_kind, source_kind, include_directories, dump, macro_defines, push_constants })
     }
 }
 
@@ -195,5 +217,5 @@ pub fn shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         Err(e) => panic!(e.replace("(s): ", "(s):\n"))
     };
 
-    codegen::reflect("Shader", content.as_binary(), input.dump).unwrap().into()
+    codegen::reflect("Shader", content.as_binary(), input.push_constants.as_ref(), input.dump).unwrap().into()
 }